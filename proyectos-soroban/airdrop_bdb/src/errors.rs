@@ -0,0 +1,43 @@
+// src/errors.rs
+use soroban_sdk::contracterror;
+
+/// Enum de errores del contrato de airdrop
+///
+/// Cada error tiene un código único para debugging en el ledger
+/// Los códigos empiezan en 1 (0 está reservado para "sin error")
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum AirdropError {
+    /// El contrato ya fue inicializado
+    /// Se lanza si se intenta llamar initialize() dos veces
+    AlreadyInitialized = 1,
+
+    /// El contrato no ha sido inicializado
+    /// Se debe llamar initialize() primero
+    NotInitialized = 2,
+
+    /// Amount debe ser mayor a 0
+    InvalidAmount = 3,
+
+    /// El deadline de claims ya pasó al momento de inicializar
+    InvalidDeadline = 4,
+
+    /// La prueba Merkle no lleva a la raíz configurada
+    /// (index, address o amount no coinciden con ninguna hoja)
+    InvalidProof = 5,
+
+    /// Esta hoja del árbol ya fue reclamada
+    AlreadyClaimed = 6,
+
+    /// El período de claims terminó
+    /// Después del deadline solo queda el sweep del operador
+    ClaimPeriodEnded = 7,
+
+    /// El período de claims sigue abierto
+    /// sweep() solo se permite después del deadline
+    ClaimPeriodActive = 8,
+
+    /// No quedan tokens en el contrato para barrer
+    NothingToSweep = 9,
+}
@@ -0,0 +1,32 @@
+// src/events.rs
+use soroban_sdk::{contractevent, Address, BytesN};
+
+/// Topics `["init", operator, token]`, data `{ claim_deadline, merkle_root }`
+#[contractevent(topics = ["init"])]
+pub struct InitEvent {
+    #[topic]
+    pub operator: Address,
+    #[topic]
+    pub token: Address,
+    pub merkle_root: BytesN<32>,
+    pub claim_deadline: u32,
+}
+
+/// Topics `["claim", address]`, data `{ amount, index }`
+#[contractevent(topics = ["claim"])]
+pub struct ClaimEvent {
+    #[topic]
+    pub address: Address,
+    pub index: u32,
+    pub amount: i128,
+}
+
+/// Topics `["sweep", operator, to]`, data = monto barrido
+#[contractevent(topics = ["sweep"], data_format = "single-value")]
+pub struct SweepEvent {
+    #[topic]
+    pub operator: Address,
+    #[topic]
+    pub to: Address,
+    pub amount: i128,
+}
@@ -0,0 +1,208 @@
+// src/lib.rs
+#![no_std]
+
+//! Airdrop de BDB con raíz Merkle
+//!
+//! El operador publica la raíz de un árbol de hojas (index, address, amount)
+//! y fondea el contrato con BDB. Cada usuario reclama con su prueba y recibe
+//! los tokens a través del `transfer` estándar de TokenBDB. Después del
+//! deadline el operador puede barrer lo que no se reclamó.
+
+use soroban_sdk::{
+    contract, contractimpl, token, Address, BytesN, Env, Vec
+};
+
+mod errors;
+mod events;
+mod merkle;
+mod storage;
+
+#[cfg(any(test, feature = "testutils"))]
+pub mod merkle_tree;
+
+#[cfg(test)]
+mod test;
+
+use errors::AirdropError;
+use events::{ClaimEvent, InitEvent, SweepEvent};
+use storage::DataKey;
+
+pub trait AirdropTrait {
+    /// Configura el airdrop
+    ///
+    /// Puede ser llamado solo una vez. Requiere autorización del operador.
+    /// - Operator: cuenta que barre el remanente después del deadline
+    /// - Token: contrato TokenBDB (o cualquier token SEP-41)
+    /// - Merkle root: raíz SHA-256 del árbol de (index, address, amount)
+    /// - Claim deadline: último ledger en que se puede reclamar
+    ///
+    /// El contrato se fondea transfiriéndole BDB a su dirección
+    fn initialize(
+        env: Env,
+        operator: Address,
+        token: Address,
+        merkle_root: BytesN<32>,
+        claim_deadline: u32
+    ) -> Result<(), AirdropError>;
+
+    /// Reclama la hoja `index` del árbol
+    ///
+    /// Verifica la prueba contra la raíz, marca el índice en el bitmap
+    /// y transfiere `amount` a `address`. Cualquiera puede enviar el
+    /// claim: los tokens siempre van a la dirección de la hoja
+    fn claim(
+        env: Env,
+        index: u32,
+        address: Address,
+        amount: i128,
+        proof: Vec<BytesN<32>>
+    ) -> Result<(), AirdropError>;
+
+    /// Transfiere a `to` todo lo que no se reclamó
+    ///
+    /// Solo después del deadline. Requiere autorización del operador.
+    /// Devuelve el monto barrido
+    fn sweep(env: Env, to: Address) -> Result<i128, AirdropError>;
+
+    // Métodos de consulta (getters)
+    fn is_claimed(env: Env, index: u32) -> bool;
+    fn merkle_root(env: Env) -> Result<BytesN<32>, AirdropError>;
+    fn claim_deadline(env: Env) -> Result<u32, AirdropError>;
+    fn operator(env: Env) -> Result<Address, AirdropError>;
+    fn token(env: Env) -> Result<Address, AirdropError>;
+}
+
+/// Estructura del contrato de airdrop
+#[contract]
+pub struct AirdropBDB;
+
+#[contractimpl]
+impl AirdropTrait for AirdropBDB {
+    fn initialize(
+        env: Env,
+        operator: Address,
+        token: Address,
+        merkle_root: BytesN<32>,
+        claim_deadline: u32
+    ) -> Result<(), AirdropError> {
+        // 1. Verificar que no esté inicializado
+        if env.storage().instance().has(&DataKey::MerkleRoot) {
+            return Err(AirdropError::AlreadyInitialized);
+        }
+
+        // 2. El operador firma la configuración
+        operator.require_auth();
+
+        // 3. Un deadline en el pasado cerraría el airdrop antes de empezar
+        if claim_deadline < env.ledger().sequence() {
+            return Err(AirdropError::InvalidDeadline);
+        }
+
+        // 4. Guardar configuración en instance storage
+        env.storage().instance().set(&DataKey::Operator, &operator);
+        env.storage().instance().set(&DataKey::Token, &token);
+        env.storage().instance().set(&DataKey::MerkleRoot, &merkle_root);
+        env.storage().instance().set(&DataKey::ClaimDeadline, &claim_deadline);
+        storage::extend_instance(&env);
+
+        // 5. Emitir evento: topics ["init", operator, token], data = { claim_deadline, merkle_root }
+        InitEvent { operator, token, merkle_root, claim_deadline }.publish(&env);
+
+        Ok(())
+    }
+
+    fn claim(
+        env: Env,
+        index: u32,
+        address: Address,
+        amount: i128,
+        proof: Vec<BytesN<32>>
+    ) -> Result<(), AirdropError> {
+        // 1. Verificar inicialización y que el período siga abierto
+        let root = Self::merkle_root(env.clone())?;
+        let deadline = Self::claim_deadline(env.clone())?;
+        if env.ledger().sequence() > deadline {
+            return Err(AirdropError::ClaimPeriodEnded);
+        }
+
+        // 2. Validaciones
+        if amount <= 0 {
+            return Err(AirdropError::InvalidAmount);
+        }
+        if storage::is_claimed(&env, index) {
+            return Err(AirdropError::AlreadyClaimed);
+        }
+
+        // 3. Verificar la prueba contra la raíz
+        let leaf = merkle::hash_leaf(&env, index, &address, amount);
+        if !merkle::verify(&env, &proof, &root, leaf) {
+            return Err(AirdropError::InvalidProof);
+        }
+
+        // 4. Marcar antes de transferir (checks-effects-interactions)
+        storage::set_claimed(&env, index, deadline);
+        storage::extend_instance(&env);
+
+        // 5. Transferir con el transfer estándar del token
+        let token = Self::token(env.clone())?;
+        token::TokenClient::new(&env, &token)
+            .transfer(&env.current_contract_address(), &address, &amount);
+
+        // 6. Emitir evento: topics ["claim", address], data = { amount, index }
+        ClaimEvent { address, index, amount }.publish(&env);
+
+        Ok(())
+    }
+
+    fn sweep(env: Env, to: Address) -> Result<i128, AirdropError> {
+        // 1. Solo el operador
+        let operator = Self::operator(env.clone())?;
+        operator.require_auth();
+
+        // 2. Solo después del deadline
+        if env.ledger().sequence() <= Self::claim_deadline(env.clone())? {
+            return Err(AirdropError::ClaimPeriodActive);
+        }
+
+        // 3. Transferir todo el remanente
+        let token = token::TokenClient::new(&env, &Self::token(env.clone())?);
+        let remaining = token.balance(&env.current_contract_address());
+        if remaining == 0 {
+            return Err(AirdropError::NothingToSweep);
+        }
+        token.transfer(&env.current_contract_address(), &to, &remaining);
+
+        // 4. Emitir evento: topics ["sweep", operator, to], data = amount
+        SweepEvent { operator, to, amount: remaining }.publish(&env);
+
+        Ok(remaining)
+    }
+
+    fn is_claimed(env: Env, index: u32) -> bool {
+        storage::is_claimed(&env, index)
+    }
+
+    fn merkle_root(env: Env) -> Result<BytesN<32>, AirdropError> {
+        env.storage().instance()
+            .get(&DataKey::MerkleRoot)
+            .ok_or(AirdropError::NotInitialized)
+    }
+
+    fn claim_deadline(env: Env) -> Result<u32, AirdropError> {
+        env.storage().instance()
+            .get(&DataKey::ClaimDeadline)
+            .ok_or(AirdropError::NotInitialized)
+    }
+
+    fn operator(env: Env) -> Result<Address, AirdropError> {
+        env.storage().instance()
+            .get(&DataKey::Operator)
+            .ok_or(AirdropError::NotInitialized)
+    }
+
+    fn token(env: Env) -> Result<Address, AirdropError> {
+        env.storage().instance()
+            .get(&DataKey::Token)
+            .ok_or(AirdropError::NotInitialized)
+    }
+}
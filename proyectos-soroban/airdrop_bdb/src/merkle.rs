@@ -0,0 +1,42 @@
+// src/merkle.rs
+use soroban_sdk::{xdr::ToXdr, Address, Bytes, BytesN, Env, Vec};
+
+/// Prefijos de dominio: una hoja nunca puede hacerse pasar por un
+/// nodo interno (ataque de segunda preimagen)
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// Hash de una hoja del árbol
+///
+/// sha256(0x00 || index (u32 big-endian) || address (XDR) || amount (i128 big-endian))
+///
+/// El helper off-chain (merkle_tree) usa esta misma función, así el
+/// árbol de los tests y la verificación on-chain no pueden divergir
+pub fn hash_leaf(env: &Env, index: u32, address: &Address, amount: i128) -> BytesN<32> {
+    let mut data = Bytes::from_array(env, &[LEAF_PREFIX]);
+    data.extend_from_array(&index.to_be_bytes());
+    data.append(&address.clone().to_xdr(env));
+    data.extend_from_array(&amount.to_be_bytes());
+    env.crypto().sha256(&data).to_bytes()
+}
+
+/// Hash de un nodo interno: sha256(0x01 || min(a, b) || max(a, b))
+///
+/// Ordenar el par hace que la prueba no necesite indicar si cada
+/// hermano va a la izquierda o a la derecha
+pub fn hash_pair(env: &Env, a: &BytesN<32>, b: &BytesN<32>) -> BytesN<32> {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    let mut data = Bytes::from_array(env, &[NODE_PREFIX]);
+    data.append(&left.clone().into());
+    data.append(&right.clone().into());
+    env.crypto().sha256(&data).to_bytes()
+}
+
+/// Recorre la prueba desde la hoja y compara con la raíz
+pub fn verify(env: &Env, proof: &Vec<BytesN<32>>, root: &BytesN<32>, leaf: BytesN<32>) -> bool {
+    let mut computed = leaf;
+    for sibling in proof.iter() {
+        computed = hash_pair(env, &computed, &sibling);
+    }
+    computed == *root
+}
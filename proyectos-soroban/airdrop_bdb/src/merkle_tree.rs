@@ -0,0 +1,71 @@
+// src/merkle_tree.rs
+//! Helper off-chain para armar el árbol del airdrop
+//!
+//! Construye la raíz y las pruebas a partir de la lista de
+//! (address, amount); el índice de cada hoja es su posición en la lista.
+//! Usa las mismas funciones de hash que el contrato (crate::merkle).
+//! Disponible en tests y con la feature `testutils`.
+
+extern crate std;
+
+use soroban_sdk::{Address, BytesN, Env, Vec};
+use std::vec::Vec as StdVec;
+
+use crate::merkle::{hash_leaf, hash_pair};
+
+/// Árbol Merkle completo, nivel por nivel (levels[0] = hojas)
+pub struct MerkleTree {
+    env: Env,
+    levels: StdVec<StdVec<BytesN<32>>>,
+}
+
+impl MerkleTree {
+    /// Arma el árbol para `entries` (la hoja i es (i, address, amount))
+    ///
+    /// Si un nivel tiene una cantidad impar de nodos, el último sube
+    /// sin hashear al nivel siguiente
+    pub fn new(env: &Env, entries: &[(Address, i128)]) -> Self {
+        assert!(!entries.is_empty(), "el árbol necesita al menos una hoja");
+
+        let leaves: StdVec<BytesN<32>> = entries
+            .iter()
+            .enumerate()
+            .map(|(index, (address, amount))| hash_leaf(env, index as u32, address, *amount))
+            .collect();
+
+        let mut levels = std::vec![leaves];
+        while levels.last().unwrap().len() > 1 {
+            let next = levels.last().unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_pair(env, left, right),
+                    [single] => single.clone(),
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        MerkleTree { env: env.clone(), levels }
+    }
+
+    /// Raíz del árbol (lo que el operador pasa a initialize)
+    pub fn root(&self) -> BytesN<32> {
+        self.levels.last().unwrap()[0].clone()
+    }
+
+    /// Prueba de inclusión de la hoja `index`: un hermano por nivel
+    /// (los niveles donde el nodo sube sin hermano no aportan nada)
+    pub fn proof(&self, index: u32) -> Vec<BytesN<32>> {
+        let mut proof = Vec::new(&self.env);
+        let mut position = index as usize;
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling = position ^ 1;
+            if sibling < level.len() {
+                proof.push_back(level[sibling].clone());
+            }
+            position /= 2;
+        }
+        proof
+    }
+}
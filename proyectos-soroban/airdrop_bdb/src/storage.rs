@@ -0,0 +1,69 @@
+// src/storage.rs
+use soroban_sdk::{contracttype, Env};
+
+/// Cantidad de índices que entran en cada palabra del bitmap
+pub const BITMAP_WORD_BITS: u32 = 128;
+
+/// Claves de almacenamiento del airdrop
+///
+/// - Instance Storage: configuración (operador, token, raíz, deadline)
+/// - Persistent Storage: bitmap de claims (crece con la cantidad de hojas)
+#[contracttype]
+pub enum DataKey {
+    /// Cuenta que configura el airdrop y barre el remanente - Instance Storage
+    Operator,
+
+    /// Contrato del token a distribuir (TokenBDB) - Instance Storage
+    Token,
+
+    /// Raíz SHA-256 del árbol de (index, address, amount) - Instance Storage
+    MerkleRoot,
+
+    /// Último ledger en que se puede reclamar - Instance Storage
+    ClaimDeadline,
+
+    /// Palabra del bitmap de claims - Persistent Storage
+    /// Palabra `index / 128`; el bit `index % 128` indica si ya se reclamó.
+    /// Un u128 por cada 128 hojas en vez de una entrada por hoja
+    ClaimedWord(u32),
+}
+
+/// TTL de las palabras del bitmap
+///
+/// Deben sobrevivir al menos hasta el deadline; si no, una palabra
+/// archivada se leería como "nadie reclamó"
+const DAY_IN_LEDGERS: u32 = 17280;
+const PERSISTENT_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const PERSISTENT_LIFETIME_THRESHOLD: u32 = PERSISTENT_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+/// Extiende el TTL del instance storage
+pub fn extend_instance(env: &Env) {
+    env.storage().instance().extend_ttl(
+        PERSISTENT_LIFETIME_THRESHOLD,
+        PERSISTENT_BUMP_AMOUNT
+    );
+}
+
+/// Devuelve true si el índice ya fue reclamado
+pub fn is_claimed(env: &Env, index: u32) -> bool {
+    let word: u128 = env.storage().persistent()
+        .get(&DataKey::ClaimedWord(index / BITMAP_WORD_BITS))
+        .unwrap_or(0);
+    word & (1u128 << (index % BITMAP_WORD_BITS)) != 0
+}
+
+/// Marca el índice como reclamado
+///
+/// El TTL se extiende hasta el deadline (con margen de la política
+/// general si el deadline está más cerca)
+pub fn set_claimed(env: &Env, index: u32, claim_deadline: u32) {
+    let key = DataKey::ClaimedWord(index / BITMAP_WORD_BITS);
+    let word: u128 = env.storage().persistent().get(&key).unwrap_or(0);
+    env.storage().persistent().set(&key, &(word | (1u128 << (index % BITMAP_WORD_BITS))));
+
+    let until_deadline = claim_deadline.saturating_sub(env.ledger().sequence());
+    let extend_to = until_deadline
+        .max(PERSISTENT_BUMP_AMOUNT)
+        .min(env.storage().max_ttl());
+    env.storage().persistent().extend_ttl(&key, extend_to, extend_to);
+}
@@ -0,0 +1,181 @@
+// src/test.rs
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use crate::merkle_tree::MerkleTree;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Env, String,
+};
+use token_bdb::{TokenBDB, TokenBDBClient};
+
+/// Ledger límite para reclamar en los tests
+const DEADLINE: u32 = 1000;
+
+/// Despliega BDB y el airdrop, arma el árbol para `entries` y fondea
+/// el contrato con el total. Devuelve (airdrop, token, operator, tree)
+fn setup<'a>(
+    env: &Env,
+    entries: &[(Address, i128)]
+) -> (AirdropBDBClient<'a>, TokenBDBClient<'a>, Address, MerkleTree) {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let token_id = env.register(TokenBDB, ());
+    let token = TokenBDBClient::new(env, &token_id);
+    token.initialize(
+        &admin,
+        &String::from_str(env, "Builder Token"),
+        &String::from_str(env, "BDB"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+
+    let tree = MerkleTree::new(env, entries);
+    let operator = Address::generate(env);
+    let airdrop_id = env.register(AirdropBDB, ());
+    let airdrop = AirdropBDBClient::new(env, &airdrop_id);
+    airdrop.initialize(&operator, &token_id, &tree.root(), &DEADLINE);
+
+    let total: i128 = entries.iter().map(|(_, amount)| amount).sum();
+    token.mint(&admin, &airdrop_id, &total);
+
+    (airdrop, token, operator, tree)
+}
+
+/// Genera `count` hojas con montos distintos
+fn entries(env: &Env, count: u32) -> std::vec::Vec<(Address, i128)> {
+    (0..count)
+        .map(|i| (Address::generate(env), 100 + i as i128))
+        .collect()
+}
+
+/// Cada usuario reclama su hoja una sola vez y recibe BDB
+#[test]
+fn test_claim() {
+    let env = Env::default();
+    let entries = entries(&env, 5);
+    let (airdrop, token, _, tree) = setup(&env, &entries);
+
+    for (index, (address, amount)) in entries.iter().enumerate() {
+        let index = index as u32;
+        assert!(!airdrop.is_claimed(&index));
+        airdrop.claim(&index, address, amount, &tree.proof(index));
+        assert!(airdrop.is_claimed(&index));
+        assert_eq!(token.balance(address), *amount);
+    }
+    assert_eq!(token.balance(&airdrop.address), 0);
+
+    // Un segundo claim de la misma hoja falla
+    let (address, amount) = &entries[2];
+    assert_eq!(
+        airdrop.try_claim(&2, address, amount, &tree.proof(2)),
+        Err(Ok(AirdropError::AlreadyClaimed))
+    );
+}
+
+/// Cambiar index, address o amount invalida la prueba
+#[test]
+fn test_claim_invalid_proof() {
+    let env = Env::default();
+    let entries = entries(&env, 4);
+    let (airdrop, token, _, tree) = setup(&env, &entries);
+
+    let (address, amount) = &entries[1];
+    let proof = tree.proof(1);
+
+    // Monto inflado
+    assert_eq!(
+        airdrop.try_claim(&1, address, &(amount + 1), &proof),
+        Err(Ok(AirdropError::InvalidProof))
+    );
+    // Otra dirección con la prueba ajena
+    assert_eq!(
+        airdrop.try_claim(&1, &Address::generate(&env), amount, &proof),
+        Err(Ok(AirdropError::InvalidProof))
+    );
+    // Otro índice
+    assert_eq!(
+        airdrop.try_claim(&3, address, amount, &proof),
+        Err(Ok(AirdropError::InvalidProof))
+    );
+
+    assert_eq!(token.balance(address), 0);
+    assert!(!airdrop.is_claimed(&1));
+}
+
+/// El bitmap marca solo el bit de cada índice, también entre palabras
+#[test]
+fn test_claimed_bitmap_across_words() {
+    let env = Env::default();
+    let entries = entries(&env, 130);
+    let (airdrop, _, _, tree) = setup(&env, &entries);
+
+    for index in [127u32, 128] {
+        let (address, amount) = &entries[index as usize];
+        airdrop.claim(&index, address, amount, &tree.proof(index));
+    }
+
+    assert!(airdrop.is_claimed(&127));
+    assert!(airdrop.is_claimed(&128));
+    assert!(!airdrop.is_claimed(&0));
+    assert!(!airdrop.is_claimed(&126));
+    assert!(!airdrop.is_claimed(&129));
+}
+
+/// Después del deadline no se reclama más y el operador barre el remanente
+#[test]
+fn test_deadline_and_sweep() {
+    let env = Env::default();
+    let entries = entries(&env, 3);
+    let (airdrop, token, operator, tree) = setup(&env, &entries);
+    let treasury = Address::generate(&env);
+
+    let (address, amount) = &entries[0];
+    airdrop.claim(&0, address, amount, &tree.proof(0));
+
+    // Antes del deadline no se puede barrer
+    assert_eq!(airdrop.try_sweep(&treasury), Err(Ok(AirdropError::ClaimPeriodActive)));
+
+    env.ledger().set_sequence_number(DEADLINE + 1);
+
+    let (address, amount) = &entries[1];
+    assert_eq!(
+        airdrop.try_claim(&1, address, amount, &tree.proof(1)),
+        Err(Ok(AirdropError::ClaimPeriodEnded))
+    );
+
+    // Queda lo de las hojas 1 y 2
+    let swept = airdrop.sweep(&treasury);
+    assert_eq!(swept, 101 + 102);
+    assert_eq!(env.auths()[0].0, operator);
+    assert_eq!(token.balance(&treasury), swept);
+    assert_eq!(token.balance(&airdrop.address), 0);
+
+    assert_eq!(airdrop.try_sweep(&treasury), Err(Ok(AirdropError::NothingToSweep)));
+}
+
+/// initialize solo una vez y con un deadline futuro
+#[test]
+fn test_initialize_validations() {
+    let env = Env::default();
+    let entries = entries(&env, 2);
+    let (airdrop, token, operator, tree) = setup(&env, &entries);
+
+    assert_eq!(
+        airdrop.try_initialize(&operator, &token.address, &tree.root(), &DEADLINE),
+        Err(Ok(AirdropError::AlreadyInitialized))
+    );
+
+    env.ledger().set_sequence_number(50);
+    let fresh = AirdropBDBClient::new(&env, &env.register(AirdropBDB, ()));
+    assert_eq!(
+        fresh.try_initialize(&operator, &token.address, &tree.root(), &49),
+        Err(Ok(AirdropError::InvalidDeadline))
+    );
+    assert_eq!(fresh.try_merkle_root(), Err(Ok(AirdropError::NotInitialized)));
+}
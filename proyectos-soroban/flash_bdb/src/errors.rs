@@ -0,0 +1,35 @@
+// src/errors.rs
+use soroban_sdk::contracterror;
+
+/// Enum de errores del contrato de préstamos flash
+///
+/// Cada error tiene un código único para debugging en el ledger
+/// Los códigos empiezan en 1 (0 está reservado para "sin error")
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum FlashError {
+    /// El contrato ya fue inicializado
+    /// Se lanza si se intenta llamar initialize() dos veces
+    AlreadyInitialized = 1,
+
+    /// El contrato no ha sido inicializado
+    /// Se debe llamar initialize() primero
+    NotInitialized = 2,
+
+    /// Amount debe ser mayor a 0
+    InvalidAmount = 3,
+
+    /// Fee mayor al máximo permitido (MAX_FEE_BPS)
+    FeeTooHigh = 4,
+
+    /// El receptor devolvió error o no implementa on_flash_loan
+    FlashLoanFailed = 5,
+
+    /// No se pudo quemar amount + fee del receptor
+    /// (falta allowance al lender o balance)
+    RepaymentFailed = 6,
+
+    /// amount + fee no entra en un i128
+    OverflowError = 7,
+}
@@ -0,0 +1,31 @@
+// src/events.rs
+use soroban_sdk::{contractevent, Address};
+
+/// Topics `["init", admin, token]`, data = fee inicial en bps
+#[contractevent(topics = ["init"], data_format = "single-value")]
+pub struct InitEvent {
+    #[topic]
+    pub admin: Address,
+    #[topic]
+    pub token: Address,
+    pub fee_bps: u32,
+}
+
+/// Topics `["flash", initiator, receiver]`, data `{ amount, fee }`
+#[contractevent(topics = ["flash"])]
+pub struct FlashLoanEvent {
+    #[topic]
+    pub initiator: Address,
+    #[topic]
+    pub receiver: Address,
+    pub amount: i128,
+    pub fee: i128,
+}
+
+/// Topics `["fee_set", admin]`, data = nuevo fee en bps
+#[contractevent(topics = ["fee_set"], data_format = "single-value")]
+pub struct FeeSetEvent {
+    #[topic]
+    pub admin: Address,
+    pub fee_bps: u32,
+}
@@ -0,0 +1,232 @@
+// src/lib.rs
+#![no_std]
+
+//! Préstamos flash de BDB
+//!
+//! El lender tiene el rol Minter en TokenBDB: mintea `amount` al
+//! receptor, invoca su hook `on_flash_loan` y al volver quema
+//! `amount + fee` con `burn_from`. BDB no está en la pila durante el
+//! hook, así que el receptor puede transferir, aprobar o llamar a un
+//! DEX que mueva BDB. La devolución es un pull autorizado: el receptor
+//! aprueba al lender por `amount + fee` dentro del hook.
+//!
+//! Despliegue: el admin de BDB le da el rol Minter al lender, sin
+//! cuota (la cuota se consume en cada préstamo y el burn no la
+//! devuelve, así que una cuota limita el volumen total prestado)
+
+use soroban_sdk::{
+    contract, contractimpl, symbol_short, token, vec, Address, Bytes, Env, IntoVal,
+    InvokeError, Symbol
+};
+
+mod errors;
+mod events;
+mod storage;
+
+#[cfg(test)]
+mod test;
+
+use errors::FlashError;
+use events::{FeeSetEvent, FlashLoanEvent, InitEvent};
+use storage::DataKey;
+
+/// Fee máximo: 10% (1000 bps), el mismo techo que el fee de transferencia
+pub const MAX_FEE_BPS: u32 = 1_000;
+
+/// 100% en basis points
+const BPS_DENOMINATOR: i128 = 10_000;
+
+pub trait FlashLenderTrait {
+    /// Configura el lender
+    ///
+    /// Puede ser llamado solo una vez. Requiere autorización del admin.
+    /// - Admin: cuenta que ajusta el fee
+    /// - Token: contrato TokenBDB (el lender necesita su rol Minter)
+    /// - Fee bps: fee sobre el monto prestado (redondeado hacia arriba),
+    ///   nunca mayor a MAX_FEE_BPS
+    fn initialize(
+        env: Env,
+        admin: Address,
+        token: Address,
+        fee_bps: u32
+    ) -> Result<(), FlashError>;
+
+    /// Préstamo flash: mintea, avisa al receptor y quema de vuelta
+    ///
+    /// Mintea `amount` a `receiver` con el `mint` de BDB (respeta pausa,
+    /// tope de supply, cuota y cuentas congeladas), invoca
+    /// `on_flash_loan(initiator, token, amount, fee, data)` en `receiver`
+    /// y al volver quema `amount + fee` con `burn_from`. El receptor debe
+    /// aprobar al lender por ese monto durante el hook; si el hook falla
+    /// o el burn no alcanza se revierte todo. El supply termina igual que
+    /// antes menos el fee (el fee se quema).
+    ///
+    /// Requiere autorización de `initiator`. El receptor recibe
+    /// `initiator` y debe rechazar préstamos de quien no conoce; si no,
+    /// cualquiera podría hacerle pagar fees
+    fn flash_loan(
+        env: Env,
+        initiator: Address,
+        receiver: Address,
+        amount: i128,
+        data: Bytes
+    ) -> Result<(), FlashError>;
+
+    /// Cambia el fee en basis points
+    ///
+    /// Nunca mayor a MAX_FEE_BPS. Requiere autorización del admin
+    fn set_fee(env: Env, fee_bps: u32) -> Result<(), FlashError>;
+
+    // Métodos de consulta (getters)
+    fn fee(env: Env) -> Result<u32, FlashError>;
+    fn admin(env: Env) -> Result<Address, FlashError>;
+    fn token(env: Env) -> Result<Address, FlashError>;
+}
+
+/// Estructura del contrato de préstamos flash
+#[contract]
+pub struct FlashBDB;
+
+#[contractimpl]
+impl FlashLenderTrait for FlashBDB {
+    fn initialize(
+        env: Env,
+        admin: Address,
+        token: Address,
+        fee_bps: u32
+    ) -> Result<(), FlashError> {
+        // 1. Verificar que no esté inicializado
+        if env.storage().instance().has(&DataKey::Token) {
+            return Err(FlashError::AlreadyInitialized);
+        }
+
+        // 2. El admin firma la configuración
+        admin.require_auth();
+
+        // 3. Validar el fee
+        if fee_bps > MAX_FEE_BPS {
+            return Err(FlashError::FeeTooHigh);
+        }
+
+        // 4. Guardar configuración en instance storage
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Token, &token);
+        env.storage().instance().set(&DataKey::FeeBps, &fee_bps);
+        storage::extend_instance(&env);
+
+        // 5. Emitir evento: topics ["init", admin, token], data = fee_bps
+        InitEvent { admin, token, fee_bps }.publish(&env);
+
+        Ok(())
+    }
+
+    fn flash_loan(
+        env: Env,
+        initiator: Address,
+        receiver: Address,
+        amount: i128,
+        data: Bytes
+    ) -> Result<(), FlashError> {
+        // 1. Quien pide el préstamo firma; el receptor decide en el hook
+        initiator.require_auth();
+
+        // 2. Validaciones
+        if amount <= 0 {
+            return Err(FlashError::InvalidAmount);
+        }
+        let token = Self::token(env.clone())?;
+        let fee = apply_bps(amount, Self::fee(env.clone())?);
+        let repayment = amount.checked_add(fee)
+            .ok_or(FlashError::OverflowError)?;
+        let lender = env.current_contract_address();
+        storage::extend_instance(&env);
+
+        // 3. Mint al receptor con el rol Minter del lender
+        //    (si BDB rechaza el mint, se revierte todo)
+        env.invoke_contract::<()>(
+            &token,
+            &symbol_short!("mint"),
+            vec![
+                &env,
+                lender.into_val(&env),
+                receiver.into_val(&env),
+                amount.into_val(&env),
+            ]
+        );
+
+        // 4. Hook del receptor: BDB no está en la pila, puede usarlo
+        let result = env.try_invoke_contract::<(), InvokeError>(
+            &receiver,
+            &Symbol::new(&env, "on_flash_loan"),
+            vec![
+                &env,
+                initiator.into_val(&env),
+                token.into_val(&env),
+                amount.into_val(&env),
+                fee.into_val(&env),
+                data.into_val(&env),
+            ]
+        );
+        if !matches!(result, Ok(Ok(()))) {
+            return Err(FlashError::FlashLoanFailed);
+        }
+
+        // 5. Devolución: quemar amount + fee con el allowance del hook
+        let repaid = token::TokenClient::new(&env, &token)
+            .try_burn_from(&lender, &receiver, &repayment);
+        if !matches!(repaid, Ok(Ok(()))) {
+            return Err(FlashError::RepaymentFailed);
+        }
+
+        // 6. Emitir evento: topics ["flash", initiator, receiver], data = { amount, fee }
+        FlashLoanEvent { initiator, receiver, amount, fee }.publish(&env);
+
+        Ok(())
+    }
+
+    fn set_fee(env: Env, fee_bps: u32) -> Result<(), FlashError> {
+        // 1. Solo el admin
+        let admin = Self::admin(env.clone())?;
+        admin.require_auth();
+
+        // 2. Validar el fee
+        if fee_bps > MAX_FEE_BPS {
+            return Err(FlashError::FeeTooHigh);
+        }
+
+        // 3. Guardar y emitir evento: topics ["fee_set", admin], data = fee_bps
+        env.storage().instance().set(&DataKey::FeeBps, &fee_bps);
+        storage::extend_instance(&env);
+        FeeSetEvent { admin, fee_bps }.publish(&env);
+
+        Ok(())
+    }
+
+    fn fee(env: Env) -> Result<u32, FlashError> {
+        env.storage().instance()
+            .get(&DataKey::FeeBps)
+            .ok_or(FlashError::NotInitialized)
+    }
+
+    fn admin(env: Env) -> Result<Address, FlashError> {
+        env.storage().instance()
+            .get(&DataKey::Admin)
+            .ok_or(FlashError::NotInitialized)
+    }
+
+    fn token(env: Env) -> Result<Address, FlashError> {
+        env.storage().instance()
+            .get(&DataKey::Token)
+            .ok_or(FlashError::NotInitialized)
+    }
+}
+
+/// ceil(amount * bps / 10_000) sin riesgo de overflow
+///
+/// Redondea hacia arriba: con fee > 0 ningún préstamo sale gratis
+/// (con floor, un préstamo de menos de 10_000 / bps no pagaba fee)
+fn apply_bps(amount: i128, bps: u32) -> i128 {
+    let bps = bps as i128;
+    let rest = (amount % BPS_DENOMINATOR) * bps;
+    (amount / BPS_DENOMINATOR) * bps + (rest + BPS_DENOMINATOR - 1) / BPS_DENOMINATOR
+}
@@ -0,0 +1,30 @@
+// src/storage.rs
+use soroban_sdk::{contracttype, Env};
+
+/// Claves de almacenamiento del lender
+///
+/// Todo es configuración: no guarda saldos ni préstamos abiertos
+/// (un préstamo empieza y termina en la misma invocación)
+#[contracttype]
+pub enum DataKey {
+    /// Cuenta que configura el fee - Instance Storage
+    Admin,
+
+    /// Contrato TokenBDB que se presta - Instance Storage
+    Token,
+
+    /// Fee en basis points sobre el monto prestado - Instance Storage
+    FeeBps,
+}
+
+const DAY_IN_LEDGERS: u32 = 17280;
+const INSTANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+/// Extiende el TTL del instance storage
+pub fn extend_instance(env: &Env) {
+    env.storage().instance().extend_ttl(
+        INSTANCE_LIFETIME_THRESHOLD,
+        INSTANCE_BUMP_AMOUNT
+    );
+}
@@ -0,0 +1,290 @@
+// src/test.rs
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Events, MockAuth, MockAuthInvoke},
+    Address, Bytes, Env, IntoVal, String,
+};
+use token_bdb::{Role, TokenBDB, TokenBDBClient};
+
+/// Fee de los tests: 0.09%
+const FEE_BPS: u32 = 9;
+
+/// DEX de prueba: compra BDB pagando 1% más
+///
+/// El vendedor transfiere primero y después llama a `sell` (el pool
+/// confía en el monto). Alcanza para que el receptor mueva BDB durante
+/// el hook y termine con ganancia después de devolver amount + fee
+mod pool {
+    use soroban_sdk::{contract, contractimpl, token, Address, Env};
+
+    #[contract]
+    pub struct MockPool;
+
+    #[contractimpl]
+    impl MockPool {
+        pub fn sell(env: Env, token: Address, seller: Address, amount: i128) {
+            token::TokenClient::new(&env, &token)
+                .transfer(&env.current_contract_address(), &seller, &(amount + amount / 100));
+        }
+    }
+}
+
+/// Receptor de prueba
+///
+/// Solo acepta préstamos iniciados por su dueño (como debe hacer
+/// cualquier receptor real). En el hook vende el préstamo en el pool
+/// y aprueba al lender por amount + fee. `data` = "reject" hace fallar
+/// el hook y "no_repay" omite el approve
+mod borrower {
+    use soroban_sdk::{
+        contract, contracterror, contractimpl, token, Address, Bytes, Env, Symbol
+    };
+
+    use super::pool::MockPoolClient;
+
+    #[contracterror]
+    #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+    #[repr(u32)]
+    pub enum BorrowerError {
+        Rejected = 1,
+        UnknownInitiator = 2,
+    }
+
+    #[contract]
+    pub struct MockBorrower;
+
+    #[contractimpl]
+    impl MockBorrower {
+        pub fn __constructor(env: Env, owner: Address, lender: Address, pool: Address) {
+            env.storage().instance().set(&Symbol::new(&env, "owner"), &owner);
+            env.storage().instance().set(&Symbol::new(&env, "lender"), &lender);
+            env.storage().instance().set(&Symbol::new(&env, "pool"), &pool);
+        }
+
+        pub fn on_flash_loan(
+            env: Env,
+            initiator: Address,
+            token: Address,
+            amount: i128,
+            fee: i128,
+            data: Bytes
+        ) -> Result<(), BorrowerError> {
+            let get = |key: &str| -> Address {
+                env.storage().instance().get(&Symbol::new(&env, key)).unwrap()
+            };
+            if initiator != get("owner") {
+                return Err(BorrowerError::UnknownInitiator);
+            }
+            if data == Bytes::from_slice(&env, b"reject") {
+                return Err(BorrowerError::Rejected);
+            }
+
+            // Usa BDB dentro del hook: vende el préstamo en el pool
+            let me = env.current_contract_address();
+            let pool = get("pool");
+            let bdb = token::TokenClient::new(&env, &token);
+            bdb.transfer(&me, &pool, &amount);
+            MockPoolClient::new(&env, &pool).sell(&token, &me, &amount);
+
+            if data != Bytes::from_slice(&env, b"no_repay") {
+                bdb.approve(&me, &get("lender"), &(amount + fee), &env.ledger().sequence());
+            }
+            env.storage().instance().set(&Symbol::new(&env, "last_loan"), &(amount, fee));
+            Ok(())
+        }
+
+        pub fn last_loan(env: Env) -> Option<(i128, i128)> {
+            env.storage().instance().get(&Symbol::new(&env, "last_loan"))
+        }
+    }
+}
+
+/// Despliega BDB, el lender (con rol Minter), el pool (fondeado) y
+/// el receptor. Devuelve (lender, token, admin, owner, receiver)
+fn setup<'a>(
+    env: &Env,
+    max_supply: Option<i128>
+) -> (FlashBDBClient<'a>, TokenBDBClient<'a>, Address, Address, Address) {
+    env.mock_all_auths();
+
+    let admin = Address::generate(env);
+    let token_id = env.register(TokenBDB, ());
+    let token = TokenBDBClient::new(env, &token_id);
+    token.initialize(
+        &admin,
+        &String::from_str(env, "Builder Token"),
+        &String::from_str(env, "BDB"),
+        &7,
+        &false,
+        &max_supply,
+        &false
+    );
+
+    let lender_id = env.register(FlashBDB, ());
+    let lender = FlashBDBClient::new(env, &lender_id);
+    lender.initialize(&admin, &token_id, &FEE_BPS);
+    token.grant_role(&admin, &Role::Minter, &lender_id);
+
+    let pool_id = env.register(pool::MockPool, ());
+    token.mint(&admin, &pool_id, &10_000);
+
+    let owner = Address::generate(env);
+    let receiver = env.register(
+        borrower::MockBorrower,
+        (owner.clone(), lender_id.clone(), pool_id)
+    );
+
+    (lender, token, admin, owner, receiver)
+}
+
+/// Préstamo y devolución en la misma invocación
+///
+/// El receptor usa BDB en el hook (lo vende en el pool) y la
+/// devolución es un burn_from con su allowance: la única firma que
+/// hace falta es la del iniciador
+#[test]
+fn test_flash_loan() {
+    let env = Env::default();
+    let (lender, token, _, owner, receiver) = setup(&env, None);
+    let bot = borrower::MockBorrowerClient::new(&env, &receiver);
+    let data = Bytes::new(&env);
+
+    lender
+        .mock_auths(&[MockAuth {
+            address: &owner,
+            invoke: &MockAuthInvoke {
+                contract: &lender.address,
+                fn_name: "flash_loan",
+                args: (owner.clone(), receiver.clone(), 100_000i128, data.clone()).into_val(&env),
+                sub_invokes: &[],
+            },
+        }])
+        .flash_loan(&owner, &receiver, &100_000, &data);
+
+    // El último evento es el resumen del lender
+    assert_eq!(
+        soroban_sdk::vec![&env, env.events().all().last().unwrap()],
+        soroban_sdk::vec![
+            &env,
+            (
+                lender.address.clone(),
+                (Symbol::new(&env, "flash"), owner.clone(), receiver.clone()).into_val(&env),
+                soroban_sdk::map![
+                    &env,
+                    (Symbol::new(&env, "amount"), 100_000i128),
+                    (Symbol::new(&env, "fee"), 90i128)
+                ].into_val(&env)
+            )
+        ]
+    );
+
+    // Ganancia del pool (1000) menos el fee (90); el fee se quema
+    assert_eq!(bot.last_loan(), Some((100_000, 90)));
+    assert_eq!(token.balance(&receiver), 910);
+    assert_eq!(token.total_supply(), 10_000 - 90);
+    assert_eq!(token.allowance(&receiver, &lender.address), 0);
+}
+
+/// El fee se redondea hacia arriba: un préstamo chico también paga
+#[test]
+fn test_flash_loan_small_amount_pays_fee() {
+    let env = Env::default();
+    let (lender, token, admin, owner, receiver) = setup(&env, None);
+
+    // 100 al 0.09% son 0.09 unidades: paga 1. El receptor tiene 10
+    // propios por si la venta en el pool no cubre el fee
+    token.mint(&admin, &receiver, &10);
+    lender.flash_loan(&owner, &receiver, &100, &Bytes::new(&env));
+
+    // Ganancia del pool (1) menos el fee (1)
+    let bot = borrower::MockBorrowerClient::new(&env, &receiver);
+    assert_eq!(bot.last_loan(), Some((100, 1)));
+    assert_eq!(token.balance(&receiver), 10);
+    assert_eq!(token.total_supply(), 10_000 + 10 - 1);
+
+    // Un préstamo de 1 unidad también paga 1
+    lender.flash_loan(&owner, &receiver, &1, &Bytes::new(&env));
+    assert_eq!(bot.last_loan(), Some((1, 1)));
+    assert_eq!(token.balance(&receiver), 9);
+}
+
+/// El préstamo se revierte si el hook falla o no hay devolución
+#[test]
+fn test_flash_loan_reverts() {
+    let env = Env::default();
+    let (lender, token, _, owner, receiver) = setup(&env, Some(50_000));
+    let no_hook = env.register(TokenBDB, ());
+
+    // El hook rechaza, no existe, o el iniciador no es conocido
+    assert_eq!(
+        lender.try_flash_loan(&owner, &receiver, &1000, &Bytes::from_slice(&env, b"reject")),
+        Err(Ok(FlashError::FlashLoanFailed))
+    );
+    assert_eq!(
+        lender.try_flash_loan(&owner, &no_hook, &1000, &Bytes::new(&env)),
+        Err(Ok(FlashError::FlashLoanFailed))
+    );
+    assert_eq!(
+        lender.try_flash_loan(&Address::generate(&env), &receiver, &1000, &Bytes::new(&env)),
+        Err(Ok(FlashError::FlashLoanFailed))
+    );
+
+    // Sin approve al lender no hay burn_from
+    assert_eq!(
+        lender.try_flash_loan(&owner, &receiver, &1000, &Bytes::from_slice(&env, b"no_repay")),
+        Err(Ok(FlashError::RepaymentFailed))
+    );
+    assert_eq!(
+        lender.try_flash_loan(&owner, &receiver, &0, &Bytes::new(&env)),
+        Err(Ok(FlashError::InvalidAmount))
+    );
+
+    // El mint respeta el tope de supply de BDB
+    assert!(lender.try_flash_loan(&owner, &receiver, &50_000, &Bytes::new(&env)).is_err());
+
+    assert_eq!(token.balance(&receiver), 0);
+    assert_eq!(token.total_supply(), 10_000);
+    assert_eq!(borrower::MockBorrowerClient::new(&env, &receiver).last_loan(), None);
+}
+
+/// Sin el rol Minter en BDB el lender no puede prestar
+#[test]
+fn test_flash_loan_requires_minter_role() {
+    let env = Env::default();
+    let (lender, token, admin, owner, receiver) = setup(&env, None);
+
+    token.revoke_role(&admin, &Role::Minter, &lender.address);
+    assert!(lender.try_flash_loan(&owner, &receiver, &1000, &Bytes::new(&env)).is_err());
+    assert_eq!(token.total_supply(), 10_000);
+}
+
+/// Configuración: fee con techo, solo el admin lo cambia, una sola inicialización
+#[test]
+fn test_fee_config() {
+    let env = Env::default();
+    let (lender, token, admin, _, _) = setup(&env, None);
+
+    assert_eq!(lender.fee(), FEE_BPS);
+    assert_eq!(lender.admin(), admin);
+    assert_eq!(lender.token(), token.address);
+
+    lender.set_fee(&MAX_FEE_BPS);
+    assert_eq!(lender.fee(), MAX_FEE_BPS);
+    assert_eq!(lender.try_set_fee(&(MAX_FEE_BPS + 1)), Err(Ok(FlashError::FeeTooHigh)));
+    assert_eq!(
+        lender.try_initialize(&admin, &token.address, &0),
+        Err(Ok(FlashError::AlreadyInitialized))
+    );
+
+    // Sin inicializar
+    let other = FlashBDBClient::new(&env, &env.register(FlashBDB, ()));
+    assert_eq!(other.try_fee(), Err(Ok(FlashError::NotInitialized)));
+    assert_eq!(
+        other.try_flash_loan(&admin, &token.address, &1, &Bytes::new(&env)),
+        Err(Ok(FlashError::NotInitialized))
+    );
+}
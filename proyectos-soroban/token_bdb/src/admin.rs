@@ -0,0 +1,39 @@
+// src/admin.rs
+use soroban_sdk::{Address, Env};
+
+use crate::errors::TokenError;
+use crate::storage::{DataKey, PendingAdmin};
+
+/// Lee el admin actual
+pub fn read_admin(env: &Env) -> Result<Address, TokenError> {
+    env.storage().instance()
+        .get(&DataKey::Admin)
+        .ok_or(TokenError::NotInitialized)
+}
+
+/// Reemplaza el admin actual
+pub fn write_admin(env: &Env, admin: &Address) {
+    env.storage().instance().set(&DataKey::Admin, admin);
+}
+
+/// Exige la firma del admin actual y lo devuelve
+pub fn require_admin(env: &Env) -> Result<Address, TokenError> {
+    let admin = read_admin(env)?;
+    admin.require_auth();
+    Ok(admin)
+}
+
+/// Lee la propuesta de traspaso pendiente, si existe
+pub fn read_pending_admin(env: &Env) -> Option<PendingAdmin> {
+    env.storage().instance().get(&DataKey::PendingAdmin)
+}
+
+/// Guarda una propuesta de traspaso (reemplaza la anterior)
+pub fn write_pending_admin(env: &Env, pending: &PendingAdmin) {
+    env.storage().instance().set(&DataKey::PendingAdmin, pending);
+}
+
+/// Elimina la propuesta de traspaso pendiente
+pub fn remove_pending_admin(env: &Env) {
+    env.storage().instance().remove(&DataKey::PendingAdmin);
+}
@@ -0,0 +1,142 @@
+// src/allowance.rs
+use soroban_sdk::{Address, Env};
+
+use crate::errors::TokenError;
+use crate::storage::{AllowanceValue, DataKey};
+
+/// Ledgers que siguen valiendo los allowances v0 después de migrar
+///
+/// v0 guardaba el monto solo (i128, sin expiración) en persistent
+/// storage. migrate() les fija como vencimiento el ledger de la
+/// migración + este plazo (~30 días a 5s por ledger): los holders
+/// tienen ese tiempo para volver a aprobar con expiración
+pub const LEGACY_ALLOWANCE_LEDGERS: u32 = 518_400;
+
+/// Vencimiento de los allowances v0 (None si el contrato no viene de v0)
+pub fn read_legacy_expiration(env: &Env) -> Option<u32> {
+    env.storage().instance().get(&DataKey::LegacyAllowanceExpiration)
+}
+
+/// Fija el vencimiento de los allowances v0 (ver migrate_v0_to_v1)
+pub fn write_legacy_expiration(env: &Env, expiration_ledger: u32) {
+    env.storage().instance().set(&DataKey::LegacyAllowanceExpiration, &expiration_ledger);
+}
+
+/// Lee el allowance guardado sin aplicar la expiración
+///
+/// Devuelve None si no existe un approve previo. Un allowance v0
+/// (i128 en persistent storage) se lee con el vencimiento que fijó
+/// migrate(); el próximo approve o gasto lo pasa a temporary storage
+fn read_allowance_value(env: &Env, from: &Address, spender: &Address) -> Option<AllowanceValue> {
+    let key = DataKey::Allowance(from.clone(), spender.clone());
+    if let Some(value) = env.storage().temporary().get(&key) {
+        return Some(value);
+    }
+    let expiration_ledger = read_legacy_expiration(env)?;
+    let amount: i128 = env.storage().persistent().get(&key)?;
+    Some(AllowanceValue { amount, expiration_ledger })
+}
+
+/// Lee cuánto puede gastar `spender` de los tokens de `from`
+///
+/// Devuelve 0 si no existe un approve previo o si ya expiró
+pub fn read_allowance(env: &Env, from: &Address, spender: &Address) -> i128 {
+    match read_allowance_value(env, from, spender) {
+        Some(value) if value.expiration_ledger >= env.ledger().sequence() => value.amount,
+        _ => 0,
+    }
+}
+
+/// Escribe el allowance entre `from` y `spender`
+///
+/// - Si amount > 0, `expiration_ledger` no puede estar en el pasado
+///   ni más lejos de lo que puede vivir una entrada temporal
+/// - Si amount es 0 eliminamos la key (revocación)
+/// - Se guarda en temporary storage con TTL hasta la expiración, como
+///   en el Stellar Asset Contract: un approve vencido desaparece solo
+///   y no paga rent
+pub fn write_allowance(
+    env: &Env,
+    from: &Address,
+    spender: &Address,
+    amount: i128,
+    expiration_ledger: u32
+) -> Result<(), TokenError> {
+    let key = DataKey::Allowance(from.clone(), spender.clone());
+
+    // El allowance v0, si lo había, queda reemplazado por este
+    if read_legacy_expiration(env).is_some() {
+        env.storage().persistent().remove(&key);
+    }
+
+    if amount == 0 {
+        env.storage().temporary().remove(&key);
+        return Ok(());
+    }
+
+    let current_ledger = env.ledger().sequence();
+    if expiration_ledger < current_ledger || expiration_ledger > env.ledger().max_live_until_ledger() {
+        return Err(TokenError::InvalidExpirationLedger);
+    }
+
+    env.storage().temporary().set(
+        &key,
+        &AllowanceValue { amount, expiration_ledger }
+    );
+    extend_until_expiration(env, &key, expiration_ledger);
+    Ok(())
+}
+
+/// Extiende el TTL de la entrada temporal hasta su ledger de expiración
+///
+/// El allowance no necesita vivir más que eso: la política general
+/// de TTL (storage::extend_persistent) no aplica aquí.
+/// `expiration_ledger` ya está validado entre el ledger actual y el
+/// máximo de la red
+fn extend_until_expiration(env: &Env, key: &DataKey, expiration_ledger: u32) {
+    let live_for = expiration_ledger - env.ledger().sequence();
+    env.storage().temporary().extend_ttl(key, live_for, live_for);
+}
+
+/// Renueva el TTL de un allowance vigente hasta su expiración
+///
+/// Un allowance v0 se pasa a temporary storage con su vencimiento.
+/// Falla con EntryNotFound si no existe o ya expiró
+pub fn extend_allowance_ttl(env: &Env, from: &Address, spender: &Address) -> Result<(), TokenError> {
+    let value = read_allowance_value(env, from, spender)
+        .filter(|value| value.expiration_ledger >= env.ledger().sequence())
+        .ok_or(TokenError::EntryNotFound)?;
+    let key = DataKey::Allowance(from.clone(), spender.clone());
+    if env.storage().temporary().has(&key) {
+        extend_until_expiration(env, &key, value.expiration_ledger);
+        return Ok(());
+    }
+    write_allowance(env, from, spender, value.amount, value.expiration_ledger)
+}
+
+/// Consume `amount` del allowance de `spender` sobre `from`
+///
+/// Falla con InsufficientAllowance si no hay approve o no alcanza
+/// (un approve vencido ya no está en temporary storage) y con
+/// AllowanceExpired si la entrada sigue viva pasado su vencimiento
+/// o si un allowance v0 pasó el suyo.
+/// Devuelve el allowance restante
+pub fn spend_allowance(
+    env: &Env,
+    from: &Address,
+    spender: &Address,
+    amount: i128
+) -> Result<i128, TokenError> {
+    let value = read_allowance_value(env, from, spender)
+        .ok_or(TokenError::InsufficientAllowance)?;
+    if value.expiration_ledger < env.ledger().sequence() {
+        return Err(TokenError::AllowanceExpired);
+    }
+    if value.amount < amount {
+        return Err(TokenError::InsufficientAllowance);
+    }
+
+    let new_allowance = value.amount - amount;
+    write_allowance(env, from, spender, new_allowance, value.expiration_ledger)?;
+    Ok(new_allowance)
+}
@@ -0,0 +1,35 @@
+// src/authorization.rs
+use soroban_sdk::{Address, Env};
+
+use crate::errors::TokenError;
+use crate::storage::{self, DataKey};
+
+/// Consulta si una cuenta está autorizada a mover tokens
+///
+/// Por defecto todas las cuentas están autorizadas
+pub fn is_authorized(env: &Env, id: &Address) -> bool {
+    env.storage().persistent()
+        .get(&DataKey::Authorized(id.clone()))
+        .unwrap_or(true)
+}
+
+/// Devuelve AccountNotAuthorized si la cuenta está congelada
+pub fn ensure_authorized(env: &Env, id: &Address) -> Result<(), TokenError> {
+    if !is_authorized(env, id) {
+        return Err(TokenError::AccountNotAuthorized);
+    }
+    Ok(())
+}
+
+/// Guarda el flag de autorización de una cuenta
+///
+/// Optimización: solo las cuentas congeladas ocupan storage
+pub fn write_authorized(env: &Env, id: &Address, authorize: bool) {
+    let key = DataKey::Authorized(id.clone());
+    if authorize {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &false);
+        storage::extend_persistent(env, &key);
+    }
+}
@@ -0,0 +1,177 @@
+// src/balance.rs
+use soroban_sdk::{Address, Env};
+
+use crate::checkpoints;
+use crate::errors::TokenError;
+use crate::holders;
+use crate::rebasing;
+use crate::storage::{self, CheckpointSeries, DataKey};
+use crate::votes;
+
+/// Lee el valor guardado en DataKey::Balance
+///
+/// En modo estándar es el balance; en modo rebasing son shares.
+/// Devuelve 0 si la cuenta nunca ha recibido tokens
+/// (o si su balance llegó a 0 y la key fue eliminada)
+pub fn read_shares(env: &Env, addr: &Address) -> i128 {
+    env.storage().persistent()
+        .get(&DataKey::Balance(addr.clone()))
+        .unwrap_or(0)
+}
+
+/// Lee el balance de una cuenta en tokens (shares × índice en modo rebasing)
+pub fn read_balance(env: &Env, addr: &Address) -> i128 {
+    rebasing::to_amount(env, read_shares(env, addr))
+}
+
+/// Escribe el valor guardado de una cuenta (shares en modo rebasing)
+///
+/// Optimización: si el balance es 0 eliminamos la key
+/// en vez de pagar rent por un valor vacío.
+/// Todos los cambios de balance pasan por aquí, así que aquí
+/// se registra el checkpoint para balance_at, se mueven los
+/// votos del delegado de la cuenta y se actualiza el registro
+/// de holders
+pub fn write_balance(env: &Env, addr: &Address, amount: i128) {
+    let previous = read_shares(env, addr);
+    checkpoints::record_balance(env, addr, previous, amount);
+    votes::on_balance_change(env, addr, amount - previous);
+    holders::on_balance_change(env, addr, amount);
+    
+    let key = DataKey::Balance(addr.clone());
+    if amount == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &amount);
+        storage::extend_persistent(env, &key);
+    }
+}
+
+/// Renueva el TTL del balance de una cuenta (y de su historial y
+/// su entrada en el registro de holders) según la política vigente
+///
+/// Falla con EntryNotFound si la cuenta no tiene balance guardado
+pub fn extend_balance_ttl(env: &Env, addr: &Address) -> Result<(), TokenError> {
+    let key = DataKey::Balance(addr.clone());
+    if !env.storage().persistent().has(&key) {
+        return Err(TokenError::EntryNotFound);
+    }
+    storage::extend_persistent(env, &key);
+    
+    checkpoints::extend_ttl(env, &CheckpointSeries::Balance(addr.clone()));
+    holders::extend_ttl(env, addr);
+    Ok(())
+}
+
+/// Suma `amount` tokens al balance de `addr` con verificación de overflow
+///
+/// En modo rebasing acredita to_shares(amount) shares.
+/// Devuelve el nuevo balance en tokens
+pub fn receive_balance(env: &Env, addr: &Address, amount: i128) -> Result<i128, TokenError> {
+    let new_shares = receive_shares(env, addr, rebasing::to_shares(env, amount))?;
+    Ok(rebasing::to_amount(env, new_shares))
+}
+
+/// Suma `shares` al valor guardado de `addr` (tokens en modo estándar)
+///
+/// Para quien ya convirtió el monto y necesita acreditar exactamente
+/// las shares que debitó (transferencias con fee, lotes).
+/// Devuelve el nuevo valor guardado
+pub fn receive_shares(env: &Env, addr: &Address, shares: i128) -> Result<i128, TokenError> {
+    let new_shares = read_shares(env, addr).checked_add(shares)
+        .ok_or(TokenError::OverflowError)?;
+    write_balance(env, addr, new_shares);
+    Ok(new_shares)
+}
+
+/// Resta `amount` tokens del balance de `addr`
+///
+/// En modo rebasing debita to_shares(amount) shares; un monto que no
+/// llega a una share falla con InvalidAmount. Falla con
+/// InsufficientBalance si la cuenta no tiene fondos suficientes.
+/// Devuelve las shares debitadas (`amount` en modo estándar): quien
+/// acredita la otra punta reparte exactamente esas shares
+pub fn spend_balance(env: &Env, addr: &Address, amount: i128) -> Result<i128, TokenError> {
+    let shares = rebasing::to_shares(env, amount);
+    if shares == 0 && amount > 0 {
+        return Err(TokenError::InvalidAmount);
+    }
+    spend_shares(env, addr, shares)?;
+    Ok(shares)
+}
+
+/// Resta `shares` del valor guardado de `addr` (tokens en modo estándar)
+///
+/// Falla con InsufficientBalance si no alcanza
+pub fn spend_shares(env: &Env, addr: &Address, shares: i128) -> Result<(), TokenError> {
+    let balance = read_shares(env, addr);
+    if balance < shares {
+        return Err(TokenError::InsufficientBalance);
+    }
+    write_balance(env, addr, balance - shares);
+    Ok(())
+}
+
+/// Lee el valor guardado en DataKey::TotalSupply
+///
+/// En modo estándar es el supply; en modo rebasing el total de shares
+pub fn read_total_shares(env: &Env) -> i128 {
+    env.storage().instance()
+        .get(&DataKey::TotalSupply)
+        .unwrap_or(0)
+}
+
+/// Lee el supply total en tokens (0 si aún no se inicializó)
+pub fn read_total_supply(env: &Env) -> i128 {
+    rebasing::to_amount(env, read_total_shares(env))
+}
+
+/// Lee el tope de supply total (None = sin tope)
+pub fn read_max_supply(env: &Env) -> Option<i128> {
+    env.storage().instance().get(&DataKey::MaxSupply)
+}
+
+/// Aumenta el supply total (mint)
+///
+/// Todas las formas de mintear pasan por aquí, así el tope de
+/// max_supply se aplica igual en todas. En modo rebasing suma
+/// to_shares(amount) al total de shares (un monto que no llega a
+/// una share falla con InvalidAmount).
+/// Devuelve el nuevo supply en tokens
+pub fn increase_supply(env: &Env, amount: i128) -> Result<i128, TokenError> {
+    let shares = rebasing::to_shares(env, amount);
+    if shares == 0 && amount > 0 {
+        return Err(TokenError::InvalidAmount);
+    }
+    increase_total_shares(env, shares)
+}
+
+/// Suma `shares` al valor guardado en DataKey::TotalSupply
+///
+/// Para lotes que acreditan shares ya convertidas: el total sube
+/// exactamente lo que suman las cuentas. Aplica el tope de
+/// max_supply (en tokens). Devuelve el nuevo supply en tokens
+pub fn increase_total_shares(env: &Env, shares: i128) -> Result<i128, TokenError> {
+    let new_shares = read_total_shares(env).checked_add(shares)
+        .ok_or(TokenError::OverflowError)?;
+    let new_total = rebasing::to_amount(env, new_shares);
+    if let Some(cap) = read_max_supply(env) {
+        if new_total > cap {
+            return Err(TokenError::SupplyCapExceeded);
+        }
+    }
+    checkpoints::record_supply(env, new_shares);
+    env.storage().instance().set(&DataKey::TotalSupply, &new_shares);
+    Ok(new_total)
+}
+
+/// Reduce el supply total (burn)
+///
+/// Devuelve el nuevo supply en tokens
+pub fn decrease_supply(env: &Env, amount: i128) -> Result<i128, TokenError> {
+    let new_shares = read_total_shares(env).checked_sub(rebasing::to_shares(env, amount))
+        .ok_or(TokenError::OverflowError)?;
+    checkpoints::record_supply(env, new_shares);
+    env.storage().instance().set(&DataKey::TotalSupply, &new_shares);
+    Ok(rebasing::to_amount(env, new_shares))
+}
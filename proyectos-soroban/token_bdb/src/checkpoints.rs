@@ -0,0 +1,252 @@
+// src/checkpoints.rs
+use soroban_sdk::{Address, Env};
+
+use crate::balance;
+use crate::errors::TokenError;
+use crate::rebasing;
+use crate::storage::{self, Checkpoint, CheckpointRange, CheckpointSeries, DataKey};
+
+/// Lee el horizonte del historial (0 = historial completo)
+pub fn read_horizon(env: &Env) -> u32 {
+    env.storage().instance()
+        .get(&DataKey::CheckpointHorizon)
+        .unwrap_or(0)
+}
+
+/// Sube el horizonte del historial
+///
+/// Nunca baja (lo ya podado no se puede recuperar) ni puede
+/// superar el ledger actual
+pub fn write_horizon(env: &Env, ledger: u32) -> Result<(), TokenError> {
+    if ledger < read_horizon(env) || ledger > env.ledger().sequence() {
+        return Err(TokenError::InvalidCheckpointHorizon);
+    }
+    env.storage().instance().set(&DataKey::CheckpointHorizon, &ledger);
+    Ok(())
+}
+
+/// Cantidad máxima de checkpoints que borra una poda explícita
+///
+/// Cada checkpoint es una entrada de storage: lo que quede se borra
+/// en la próxima llamada o, de a uno, con cada escritura de la serie
+pub const MAX_PRUNE_PER_CALL: u32 = 100;
+
+fn read_range(env: &Env, series: &CheckpointSeries) -> CheckpointRange {
+    env.storage().persistent()
+        .get(&DataKey::Checkpoints(series.clone()))
+        .unwrap_or(CheckpointRange { start: 0, end: 0 })
+}
+
+fn write_range(env: &Env, series: &CheckpointSeries, range: &CheckpointRange) {
+    let key = DataKey::Checkpoints(series.clone());
+    env.storage().persistent().set(&key, range);
+    storage::extend_persistent(env, &key);
+}
+
+fn read_at(env: &Env, series: &CheckpointSeries, index: u32) -> Checkpoint {
+    env.storage().persistent()
+        .get(&DataKey::CheckpointAt(series.clone(), index))
+        .unwrap()
+}
+
+fn write_at(env: &Env, series: &CheckpointSeries, index: u32, checkpoint: &Checkpoint) {
+    let key = DataKey::CheckpointAt(series.clone(), index);
+    env.storage().persistent().set(&key, checkpoint);
+    storage::extend_persistent(env, &key);
+}
+
+/// Índice del primer checkpoint con `ledger > target` (búsqueda
+/// binaria sobre las entradas: O(log n) lecturas)
+fn upper_bound(env: &Env, series: &CheckpointSeries, range: &CheckpointRange, target: u32) -> u32 {
+    let (mut low, mut high) = (range.start, range.end);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if read_at(env, series, mid).ledger <= target {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
+
+/// Descarta hasta `max` checkpoints anteriores al horizonte
+///
+/// Conserva el último checkpoint <= horizonte: es el valor vigente
+/// en el horizonte y sin él las consultas posteriores devolverían 0.
+/// Devuelve cuántos checkpoints se eliminaron
+fn trim(env: &Env, series: &CheckpointSeries, range: &mut CheckpointRange, max: u32) -> u32 {
+    let horizon = read_horizon(env);
+    if horizon == 0 {
+        return 0;
+    }
+    let mut removed = 0;
+    while removed < max
+        && range.end - range.start >= 2
+        && read_at(env, series, range.start + 1).ledger <= horizon
+    {
+        env.storage().persistent().remove(&DataKey::CheckpointAt(series.clone(), range.start));
+        range.start += 1;
+        removed += 1;
+    }
+    removed
+}
+
+/// Registra el nuevo valor en el ledger actual
+///
+/// - Un checkpoint por ledger: varios cambios en el mismo ledger
+///   sobrescriben el último
+/// - Si la serie está vacía y el valor previo no era 0 (balance anterior
+///   a los checkpoints, p. ej. un despliegue actualizado) se siembra
+///   primero ese valor en el ledger 0
+/// - Aprovecha la escritura para descartar un checkpoint anterior al
+///   horizonte (así el historial podado no crece con el uso)
+pub fn record(env: &Env, series: &CheckpointSeries, previous: i128, value: i128) {
+    let ledger = env.ledger().sequence();
+    let mut range = read_range(env, series);
+
+    if range.start == range.end {
+        if previous != 0 {
+            write_at(env, series, range.end, &Checkpoint { ledger: 0, value: previous });
+            range.end += 1;
+        }
+    } else {
+        let last = read_at(env, series, range.end - 1);
+        if last.value == value {
+            return;
+        }
+        if last.ledger == ledger {
+            write_at(env, series, range.end - 1, &Checkpoint { ledger, value });
+            return;
+        }
+    }
+    write_at(env, series, range.end, &Checkpoint { ledger, value });
+    range.end += 1;
+    trim(env, series, &mut range, 1);
+    write_range(env, series, &range);
+}
+
+/// Último valor registrado en la serie (None si está vacía)
+pub fn latest(env: &Env, series: &CheckpointSeries) -> Option<i128> {
+    let range = read_range(env, series);
+    if range.start == range.end {
+        return None;
+    }
+    Some(read_at(env, series, range.end - 1).value)
+}
+
+/// Renueva el TTL del rango y del último checkpoint de la serie
+///
+/// Son las entradas que lee cada escritura. Los checkpoints viejos
+/// no se renuevan: si se archivan, las consultas que los necesiten
+/// los restauran (o la poda los descarta)
+pub fn extend_ttl(env: &Env, series: &CheckpointSeries) {
+    let range_key = DataKey::Checkpoints(series.clone());
+    if !env.storage().persistent().has(&range_key) {
+        return;
+    }
+    storage::extend_persistent(env, &range_key);
+    let range = read_range(env, series);
+    if range.start < range.end {
+        storage::extend_persistent(env, &DataKey::CheckpointAt(series.clone(), range.end - 1));
+    }
+}
+
+/// Registra el nuevo balance de `addr` (shares en modo rebasing)
+///
+/// Se llama desde balance::write_balance con el valor previo
+pub fn record_balance(env: &Env, addr: &Address, previous: i128, new_balance: i128) {
+    record(env, &CheckpointSeries::Balance(addr.clone()), previous, new_balance);
+}
+
+/// Registra el nuevo supply total (total de shares en modo rebasing)
+///
+/// Se llama desde increase_supply / decrease_supply ANTES de guardarlo
+pub fn record_supply(env: &Env, new_total: i128) {
+    record(
+        env,
+        &CheckpointSeries::Supply,
+        balance::read_total_shares(env),
+        new_total
+    );
+}
+
+/// Valor vigente al cierre de `ledger`
+///
+/// El ledger actual todavía no cerró: se puede mover en esta misma
+/// transacción (p. ej. transferir y votar en el mismo ledger), así
+/// que solo se aceptan ledgers anteriores.
+/// Sin checkpoints el valor nunca cambió: vale el actual
+pub fn value_at(
+    env: &Env,
+    series: &CheckpointSeries,
+    ledger: u32,
+    current: i128
+) -> Result<i128, TokenError> {
+    if ledger >= env.ledger().sequence() {
+        return Err(TokenError::FutureLedger);
+    }
+    if ledger < read_horizon(env) {
+        return Err(TokenError::HistoryPruned);
+    }
+
+    let range = read_range(env, series);
+    if range.start == range.end {
+        return Ok(current);
+    }
+    match upper_bound(env, series, &range, ledger) {
+        index if index == range.start => Ok(0),
+        index => Ok(read_at(env, series, index - 1).value),
+    }
+}
+
+/// Pasa a tokens un valor histórico guardado en shares
+///
+/// En modo rebasing usa el índice vigente en ese mismo ledger
+fn shares_to_amount_at(env: &Env, shares: i128, ledger: u32) -> Result<i128, TokenError> {
+    if !rebasing::is_enabled(env) {
+        return Ok(shares);
+    }
+    let index = value_at(env, &CheckpointSeries::RebaseIndex, ledger, rebasing::read_index(env))?;
+    Ok(rebasing::to_amount_at(shares, index))
+}
+
+/// Balance de `addr` al cierre de `ledger`
+pub fn balance_at(env: &Env, addr: &Address, ledger: u32) -> Result<i128, TokenError> {
+    let shares = value_at(
+        env,
+        &CheckpointSeries::Balance(addr.clone()),
+        ledger,
+        balance::read_shares(env, addr)
+    )?;
+    shares_to_amount_at(env, shares, ledger)
+}
+
+/// Supply total al cierre de `ledger`
+pub fn total_supply_at(env: &Env, ledger: u32) -> Result<i128, TokenError> {
+    let shares = value_at(env, &CheckpointSeries::Supply, ledger, balance::read_total_shares(env))?;
+    shares_to_amount_at(env, shares, ledger)
+}
+
+/// Poda el historial de `series` hasta el horizonte
+///
+/// Borra como máximo MAX_PRUNE_PER_CALL checkpoints.
+/// Devuelve cuántos se eliminaron
+pub fn prune(env: &Env, series: &CheckpointSeries) -> u32 {
+    let mut range = read_range(env, series);
+    let removed = trim(env, series, &mut range, MAX_PRUNE_PER_CALL);
+    if removed > 0 {
+        write_range(env, series, &range);
+    }
+    removed
+}
+
+/// Poda el historial de balances de `addr`
+pub fn prune_balance(env: &Env, addr: &Address) -> u32 {
+    prune(env, &CheckpointSeries::Balance(addr.clone()))
+}
+
+/// Poda el historial del supply total
+pub fn prune_supply(env: &Env) -> u32 {
+    prune(env, &CheckpointSeries::Supply)
+}
@@ -44,4 +44,105 @@ pub enum TokenError {
     /// Nombre o símbolo inválido (vacío o muy largo)
     /// Validación de metadatos en initialize()
     InvalidMetadata = 9,
-}
\ No newline at end of file
+    
+    /// Ledger de expiración inválido en approve()
+    /// Si amount > 0 debe ser >= al ledger actual y no mayor al
+    /// máximo que puede vivir una entrada en la red
+    InvalidExpirationLedger = 10,
+    
+    /// El allowance existe pero ya expiró
+    /// Una entrada que sigue viva pasado su vencimiento, o un allowance
+    /// v0 pasado el que fijó migrate(); un approve vencido cuyo TTL ya
+    /// terminó falla como InsufficientAllowance
+    AllowanceExpired = 11,
+    
+    /// No hay un traspaso de admin pendiente
+    /// accept_admin() / cancel_admin_proposal() sin propose_admin()
+    NoPendingAdmin = 12,
+    
+    /// La propuesta de admin venció (deadline_ledger superado)
+    /// o se propuso con un deadline en el pasado
+    AdminProposalExpired = 13,
+    
+    /// La cuenta no tiene el rol requerido
+    /// Ver roles.rs: grant_role / has_role. También: la clave de
+    /// permit() no es la de la cuenta owner
+    Unauthorized = 14,
+    
+    /// El contrato está pausado
+    /// transfer, transfer_from, mint y burn fallan hasta unpause()
+    ContractPaused = 15,
+    
+    /// La cuenta está congelada (set_authorized = false)
+    /// No puede enviar, recibir, recibir mints ni quemar
+    AccountNotAuthorized = 16,
+    
+    /// El token se creó sin clawback
+    /// clawback() no está disponible y nunca lo estará
+    ClawbackDisabled = 17,
+    
+    /// El mint superaría el tope de max_supply
+    SupplyCapExceeded = 18,
+    
+    /// Tope de supply inválido
+    /// <= 0, mayor al tope actual o menor al supply emitido
+    InvalidSupplyCap = 19,
+    
+    /// El minter agotó su cuota del período actual
+    MinterQuotaExceeded = 20,
+    
+    /// Cuota inválida: limit < 0 o period_ledgers = 0
+    InvalidQuota = 21,
+    
+    /// El storage ya está en la versión actual
+    /// migrate() no tiene migraciones pendientes
+    AlreadyMigrated = 22,
+    
+    /// Política de TTL inválida
+    /// threshold > extend_to o extend_to mayor al máximo de la red
+    InvalidTtlConfig = 23,
+    
+    /// No existe la entrada cuyo TTL se quiere extender
+    EntryNotFound = 24,
+    
+    /// Lote vacío en batch_transfer / batch_mint
+    EmptyBatch = 25,
+    
+    /// Lote con más de MAX_BATCH entradas en batch_transfer / batch_mint
+    /// (no entraría en los límites de escrituras de una transacción)
+    BatchTooLarge = 26,
+    
+    /// balance_at / total_supply_at / get_past_votes con un ledger
+    /// que aún no cerró (el actual incluido)
+    FutureLedger = 27,
+    
+    /// El historial de ese ledger ya fue podado
+    /// (ledger anterior al horizonte de checkpoints)
+    HistoryPruned = 28,
+    
+    /// Horizonte inválido: menor al actual o mayor al ledger en curso
+    InvalidCheckpointHorizon = 29,
+    
+    /// El nonce de permit() no es el próximo nonce del owner
+    /// (firma ya usada o fuera de orden)
+    InvalidNonce = 30,
+    
+    /// La firma de permit() venció (deadline_ledger superado)
+    PermitExpired = 31,
+    
+    /// Fee de transferencia mayor al máximo permitido (MAX_FEE_BPS)
+    FeeTooHigh = 32,
+    
+    /// El contrato receptor de transfer_and_call devolvió error
+    /// o no implementa on_token_received
+    ReceiverRejected = 33,
+    
+    /// rebase() en un token creado sin modo rebasing
+    NotRebasing = 34,
+    
+    /// Índice de rebase no positivo
+    InvalidRebaseIndex = 35,
+    
+    /// holders() con limit 0 o mayor a MAX_HOLDERS_PAGE
+    InvalidPageLimit = 36,
+}
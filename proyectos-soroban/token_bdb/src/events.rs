@@ -0,0 +1,839 @@
+// src/events.rs
+//! Esquema de eventos de BDB
+//!
+//! Todos los eventos del contrato se definen aquí con
+//! `#[contractevent]`: un struct por evento, con los campos `#[topic]`
+//! en los topics (en orden) y el resto en la data. Cada evento queda
+//! además descripto en el spec del contrato.
+//!
+//! - Eventos SEP-41 (transfer, mint, burn, approve, clawback,
+//!   set_authorized): topics y data exactamente como fija el estándar,
+//!   ej: `["transfer", from, to]`, data = amount. No se agrega nada al
+//!   final: esa posición es la del asset en los eventos del Stellar
+//!   Asset Contract (CAP-67) y los indexers la leen como tal. Lo que
+//!   BDB agrega a un evento estándar (el spender de burn_from, quién
+//!   hizo el clawback) va en un evento propio que lo acompaña
+//! - Eventos propios de BDB: `[nombre, campos indexados..., versión]`,
+//!   data = map con el resto de los campos. La versión
+//!   (EVENT_SCHEMA_VERSION) es siempre el último topic, así un indexer
+//!   sabe con qué esquema leer cada evento. También se publica en la
+//!   metadata del contrato (`contractmeta!`, key `event_schema`)
+//!
+//! `TokenEvent::decode` hace el camino inverso: convierte los
+//! `(topics, data)` crudos de un evento en el struct tipado. Sirve para
+//! tests y para herramientas off-chain (indexers, scripts)
+use soroban_sdk::{
+    contractevent, symbol_short, vec, Address, BytesN, Env, Event, Map, Symbol, TryFromVal, Val, Vec
+};
+
+pub use crate::storage::{Role, TokenMetadata, TtlConfig};
+
+/// Versión del esquema de eventos (último topic de los eventos de BDB)
+///
+/// Subirla al cambiar topics o data de algún evento existente, junto
+/// con el `contractmeta!` de lib.rs (key `event_schema`); un test
+/// verifica que coincidan
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
+// ---------------------------------------------------------------
+// Eventos SEP-41
+// ---------------------------------------------------------------
+
+/// `["transfer", from, to]`, data = amount
+#[contractevent(topics = ["transfer"], data_format = "single-value")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferEvent {
+    #[topic]
+    pub from: Address,
+    #[topic]
+    pub to: Address,
+    pub amount: i128,
+}
+
+/// `["transfer", from, to]`, data = `{ amount, to_muxed_id }`
+///
+/// Transfer a una dirección muxed (CAP-67): `to` es la dirección base
+#[contractevent(topics = ["transfer"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferMuxedEvent {
+    #[topic]
+    pub from: Address,
+    #[topic]
+    pub to: Address,
+    pub to_muxed_id: u64,
+    pub amount: i128,
+}
+
+/// `["mint", to]`, data = amount
+#[contractevent(topics = ["mint"], data_format = "single-value")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MintEvent {
+    #[topic]
+    pub to: Address,
+    pub amount: i128,
+}
+
+/// `["burn", from]`, data = amount
+#[contractevent(topics = ["burn"], data_format = "single-value")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BurnEvent {
+    #[topic]
+    pub from: Address,
+    pub amount: i128,
+}
+
+/// `["approve", from, spender]`, data = `[amount, expiration_ledger]`
+#[contractevent(topics = ["approve"], data_format = "vec")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ApproveEvent {
+    #[topic]
+    pub from: Address,
+    #[topic]
+    pub spender: Address,
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+/// `["clawback", from]`, data = amount
+#[contractevent(topics = ["clawback"], data_format = "single-value")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClawbackEvent {
+    #[topic]
+    pub from: Address,
+    pub amount: i128,
+}
+
+/// `["set_authorized", id]`, data = authorize
+#[contractevent(topics = ["set_authorized"], data_format = "single-value")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetAuthorizedEvent {
+    #[topic]
+    pub id: Address,
+    pub authorize: bool,
+}
+
+// ---------------------------------------------------------------
+// Eventos propios de BDB (data = map con los campos que no son
+// topic, la versión como último topic)
+// ---------------------------------------------------------------
+
+/// `["init", admin, version]`
+#[contractevent(topics = ["init"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InitEvent {
+    #[topic]
+    pub admin: Address,
+    pub metadata: TokenMetadata,
+    #[topic]
+    pub version: u32,
+}
+
+/// `["mint_qta", minter, to, version]`: mint que consumió cuota
+#[contractevent(topics = ["mint_qta"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MintQuotaEvent {
+    #[topic]
+    pub minter: Address,
+    #[topic]
+    pub to: Address,
+    pub amount: i128,
+    pub remaining: i128,
+    #[topic]
+    pub version: u32,
+}
+
+/// `["burn_from", from, spender, version]`: acompaña al burn de burn_from
+#[contractevent(topics = ["burn_from"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BurnFromEvent {
+    #[topic]
+    pub from: Address,
+    #[topic]
+    pub spender: Address,
+    pub amount: i128,
+    #[topic]
+    pub version: u32,
+}
+
+/// `["clawbk_by", caller, from, version]`: acompaña al clawback y
+/// dice quién lo ejecutó
+#[contractevent(topics = ["clawbk_by"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClawbackCallerEvent {
+    #[topic]
+    pub caller: Address,
+    #[topic]
+    pub from: Address,
+    pub amount: i128,
+    #[topic]
+    pub version: u32,
+}
+
+/// `["quota_set", minter, version]`
+#[contractevent(topics = ["quota_set"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QuotaSetEvent {
+    #[topic]
+    pub minter: Address,
+    pub limit: i128,
+    pub period_ledgers: u32,
+    #[topic]
+    pub version: u32,
+}
+
+/// `["quota_rm", minter, version]`
+#[contractevent(topics = ["quota_rm"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QuotaRemovedEvent {
+    #[topic]
+    pub minter: Address,
+    #[topic]
+    pub version: u32,
+}
+
+/// `["adm_prop", current, new_admin, version]`
+#[contractevent(topics = ["adm_prop"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminProposedEvent {
+    #[topic]
+    pub current: Address,
+    #[topic]
+    pub new_admin: Address,
+    pub deadline_ledger: Option<u32>,
+    #[topic]
+    pub version: u32,
+}
+
+/// `["adm_acpt", previous, new_admin, version]`
+#[contractevent(topics = ["adm_acpt"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminAcceptedEvent {
+    #[topic]
+    pub previous: Address,
+    #[topic]
+    pub new_admin: Address,
+    #[topic]
+    pub version: u32,
+}
+
+/// `["adm_cncl", current, proposed, version]`
+#[contractevent(topics = ["adm_cncl"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminProposalCancelledEvent {
+    #[topic]
+    pub current: Address,
+    #[topic]
+    pub proposed: Address,
+    #[topic]
+    pub version: u32,
+}
+
+/// `["role_grnt", role, account, version]`
+#[contractevent(topics = ["role_grnt"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleGrantedEvent {
+    #[topic]
+    pub role: Role,
+    #[topic]
+    pub account: Address,
+    pub caller: Address,
+    #[topic]
+    pub version: u32,
+}
+
+/// `["role_rvk", role, account, version]`
+#[contractevent(topics = ["role_rvk"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleRevokedEvent {
+    #[topic]
+    pub role: Role,
+    #[topic]
+    pub account: Address,
+    pub caller: Address,
+    #[topic]
+    pub version: u32,
+}
+
+/// `["role_rnc", role, account, version]`
+#[contractevent(topics = ["role_rnc"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoleRenouncedEvent {
+    #[topic]
+    pub role: Role,
+    #[topic]
+    pub account: Address,
+    #[topic]
+    pub version: u32,
+}
+
+/// `["paused", caller, version]`
+#[contractevent(topics = ["paused"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PausedEvent {
+    #[topic]
+    pub caller: Address,
+    #[topic]
+    pub version: u32,
+}
+
+/// `["unpaused", caller, version]`
+#[contractevent(topics = ["unpaused"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnpausedEvent {
+    #[topic]
+    pub caller: Address,
+    #[topic]
+    pub version: u32,
+}
+
+/// `["max_sup", admin, version]`
+#[contractevent(topics = ["max_sup"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MaxSupplySetEvent {
+    #[topic]
+    pub admin: Address,
+    pub max_supply: i128,
+    #[topic]
+    pub version: u32,
+}
+
+/// `["upgrade", admin, version]`
+#[contractevent(topics = ["upgrade"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradedEvent {
+    #[topic]
+    pub admin: Address,
+    pub new_wasm_hash: BytesN<32>,
+    #[topic]
+    pub version: u32,
+}
+
+/// `["migrated", version]`: un paso de migración de storage
+#[contractevent(topics = ["migrated"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigratedEvent {
+    pub from_version: u32,
+    pub to_version: u32,
+    #[topic]
+    pub version: u32,
+}
+
+/// `["batch_tx", from, version]`
+#[contractevent(topics = ["batch_tx"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchTransferEvent {
+    #[topic]
+    pub from: Address,
+    pub count: u32,
+    pub total: i128,
+    #[topic]
+    pub version: u32,
+}
+
+/// `["batch_mnt", minter, version]`
+#[contractevent(topics = ["batch_mnt"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchMintEvent {
+    #[topic]
+    pub minter: Address,
+    pub count: u32,
+    pub total: i128,
+    pub remaining_quota: Option<i128>,
+    #[topic]
+    pub version: u32,
+}
+
+/// `["ckpt_hzn", admin, version]`
+#[contractevent(topics = ["ckpt_hzn"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CheckpointHorizonEvent {
+    #[topic]
+    pub admin: Address,
+    pub ledger: u32,
+    #[topic]
+    pub version: u32,
+}
+
+/// `["delgt_chg", delegator, version]`
+#[contractevent(topics = ["delgt_chg"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DelegateChangedEvent {
+    #[topic]
+    pub delegator: Address,
+    pub previous: Option<Address>,
+    pub delegatee: Address,
+    #[topic]
+    pub version: u32,
+}
+
+/// `["votes_chg", delegatee, version]`
+#[contractevent(topics = ["votes_chg"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VotesChangedEvent {
+    #[topic]
+    pub delegatee: Address,
+    pub previous: i128,
+    pub new_votes: i128,
+    #[topic]
+    pub version: u32,
+}
+
+/// `["xfer_fee", from, to, version]`: acompaña al transfer que cobró fee
+#[contractevent(topics = ["xfer_fee"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferFeeEvent {
+    #[topic]
+    pub from: Address,
+    #[topic]
+    pub to: Address,
+    pub gross: i128,
+    pub fee: i128,
+    pub net: i128,
+    #[topic]
+    pub version: u32,
+}
+
+/// `["fee_set", admin, version]`
+#[contractevent(topics = ["fee_set"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeSetEvent {
+    #[topic]
+    pub admin: Address,
+    pub fee_bps: u32,
+    pub treasury: Address,
+    #[topic]
+    pub version: u32,
+}
+
+/// `["fee_exmpt", admin, account, version]`
+#[contractevent(topics = ["fee_exmpt"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeExemptEvent {
+    #[topic]
+    pub admin: Address,
+    #[topic]
+    pub account: Address,
+    pub exempt: bool,
+    #[topic]
+    pub version: u32,
+}
+
+/// `["rebase", caller, version]`
+#[contractevent(topics = ["rebase"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RebaseEvent {
+    #[topic]
+    pub caller: Address,
+    pub old_index: i128,
+    pub new_index: i128,
+    #[topic]
+    pub version: u32,
+}
+
+/// `["meta_upd", caller, version]`
+#[contractevent(topics = ["meta_upd"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MetadataUpdatedEvent {
+    #[topic]
+    pub caller: Address,
+    pub metadata: TokenMetadata,
+    #[topic]
+    pub version: u32,
+}
+
+/// `["ttl_cfg", admin, version]`
+#[contractevent(topics = ["ttl_cfg"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TtlConfigEvent {
+    #[topic]
+    pub admin: Address,
+    pub config: TtlConfig,
+    #[topic]
+    pub version: u32,
+}
+
+// ---------------------------------------------------------------
+// Decodificación
+// ---------------------------------------------------------------
+
+/// Cualquier evento que emite BDB
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TokenEvent {
+    Transfer(TransferEvent),
+    TransferMuxed(TransferMuxedEvent),
+    Mint(MintEvent),
+    Burn(BurnEvent),
+    Approve(ApproveEvent),
+    Clawback(ClawbackEvent),
+    SetAuthorized(SetAuthorizedEvent),
+    Init(InitEvent),
+    MintQuota(MintQuotaEvent),
+    BurnFrom(BurnFromEvent),
+    ClawbackCaller(ClawbackCallerEvent),
+    QuotaSet(QuotaSetEvent),
+    QuotaRemoved(QuotaRemovedEvent),
+    AdminProposed(AdminProposedEvent),
+    AdminAccepted(AdminAcceptedEvent),
+    AdminProposalCancelled(AdminProposalCancelledEvent),
+    RoleGranted(RoleGrantedEvent),
+    RoleRevoked(RoleRevokedEvent),
+    RoleRenounced(RoleRenouncedEvent),
+    Paused(PausedEvent),
+    Unpaused(UnpausedEvent),
+    MaxSupplySet(MaxSupplySetEvent),
+    Upgraded(UpgradedEvent),
+    Migrated(MigratedEvent),
+    BatchTransfer(BatchTransferEvent),
+    BatchMint(BatchMintEvent),
+    CheckpointHorizon(CheckpointHorizonEvent),
+    DelegateChanged(DelegateChangedEvent),
+    VotesChanged(VotesChangedEvent),
+    TransferFee(TransferFeeEvent),
+    FeeSet(FeeSetEvent),
+    FeeExempt(FeeExemptEvent),
+    Rebase(RebaseEvent),
+    MetadataUpdated(MetadataUpdatedEvent),
+    TtlConfig(TtlConfigEvent),
+}
+
+/// Por qué no se pudo decodificar un evento
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DecodeError {
+    /// El nombre (primer topic) no es un evento de BDB
+    UnknownEvent,
+    /// Topics o data no tienen el formato del evento
+    /// (incluye los eventos de BDB sin versión)
+    Malformed,
+    /// Evento de BDB de otra versión del esquema (último topic)
+    UnsupportedVersion(u32),
+}
+
+/// Topics y data de un evento ya convertidos a Val
+fn raw(env: &Env, event: &impl Event) -> (Vec<Val>, Val) {
+    (event.topics(env), event.data(env))
+}
+
+/// Lee el topic `index` como `T`
+fn topic<T: TryFromVal<Env, Val>>(env: &Env, topics: &Vec<Val>, index: u32) -> Result<T, DecodeError> {
+    topics.get(index)
+        .and_then(|value| T::try_from_val(env, &value).ok())
+        .ok_or(DecodeError::Malformed)
+}
+
+/// Lee la data como `T`
+fn value<T: TryFromVal<Env, Val>>(env: &Env, data: &Val) -> Result<T, DecodeError> {
+    T::try_from_val(env, data).map_err(|_| DecodeError::Malformed)
+}
+
+/// Lee el campo `name` de una data en formato map
+fn field<T: TryFromVal<Env, Val>>(env: &Env, fields: &Map<Symbol, Val>, name: &str) -> Result<T, DecodeError> {
+    fields.get(Symbol::new(env, name))
+        .and_then(|value| T::try_from_val(env, &value).ok())
+        .ok_or(DecodeError::Malformed)
+}
+
+impl TokenEvent {
+    /// Topics y data crudos del evento (lo que publish() emite)
+    pub fn to_raw(&self, env: &Env) -> (Vec<Val>, Val) {
+        match self {
+            TokenEvent::Transfer(e) => raw(env, e),
+            TokenEvent::TransferMuxed(e) => raw(env, e),
+            TokenEvent::Mint(e) => raw(env, e),
+            TokenEvent::Burn(e) => raw(env, e),
+            TokenEvent::Approve(e) => raw(env, e),
+            TokenEvent::Clawback(e) => raw(env, e),
+            TokenEvent::SetAuthorized(e) => raw(env, e),
+            TokenEvent::Init(e) => raw(env, e),
+            TokenEvent::MintQuota(e) => raw(env, e),
+            TokenEvent::BurnFrom(e) => raw(env, e),
+            TokenEvent::ClawbackCaller(e) => raw(env, e),
+            TokenEvent::QuotaSet(e) => raw(env, e),
+            TokenEvent::QuotaRemoved(e) => raw(env, e),
+            TokenEvent::AdminProposed(e) => raw(env, e),
+            TokenEvent::AdminAccepted(e) => raw(env, e),
+            TokenEvent::AdminProposalCancelled(e) => raw(env, e),
+            TokenEvent::RoleGranted(e) => raw(env, e),
+            TokenEvent::RoleRevoked(e) => raw(env, e),
+            TokenEvent::RoleRenounced(e) => raw(env, e),
+            TokenEvent::Paused(e) => raw(env, e),
+            TokenEvent::Unpaused(e) => raw(env, e),
+            TokenEvent::MaxSupplySet(e) => raw(env, e),
+            TokenEvent::Upgraded(e) => raw(env, e),
+            TokenEvent::Migrated(e) => raw(env, e),
+            TokenEvent::BatchTransfer(e) => raw(env, e),
+            TokenEvent::BatchMint(e) => raw(env, e),
+            TokenEvent::CheckpointHorizon(e) => raw(env, e),
+            TokenEvent::DelegateChanged(e) => raw(env, e),
+            TokenEvent::VotesChanged(e) => raw(env, e),
+            TokenEvent::TransferFee(e) => raw(env, e),
+            TokenEvent::FeeSet(e) => raw(env, e),
+            TokenEvent::FeeExempt(e) => raw(env, e),
+            TokenEvent::Rebase(e) => raw(env, e),
+            TokenEvent::MetadataUpdated(e) => raw(env, e),
+            TokenEvent::TtlConfig(e) => raw(env, e),
+        }
+    }
+
+    /// Convierte los `(topics, data)` crudos de un evento en el evento tipado
+    ///
+    /// Falla si el evento no es de BDB, si es de otra versión del esquema
+    /// o si no tiene exactamente su formato (topics o campos de más o
+    /// de menos)
+    pub fn decode(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<TokenEvent, DecodeError> {
+        // 1. Armar el evento a partir del nombre
+        let event = Self::decode_fields(env, topics, data)?;
+
+        // 2. Volver a codificarlo tiene que dar lo mismo: así se rechazan
+        //    topics o campos que el esquema no tiene
+        let (raw_topics, raw_data) = event.to_raw(env);
+        if raw_topics != *topics || vec![env, raw_data] != vec![env, *data] {
+            return Err(DecodeError::Malformed);
+        }
+
+        Ok(event)
+    }
+
+    fn decode_fields(env: &Env, topics: &Vec<Val>, data: &Val) -> Result<TokenEvent, DecodeError> {
+        // 1. El nombre va en el primer topic
+        let name: Symbol = topic(env, topics, 0)?;
+        let is = |expected: Symbol| name == expected;
+
+        // 2. SEP-41: se arma con los topics + la data del estándar
+        if is(symbol_short!("transfer")) {
+            let from = topic(env, topics, 1)?;
+            let to = topic(env, topics, 2)?;
+            return Ok(match value::<i128>(env, data) {
+                Ok(amount) => TokenEvent::Transfer(TransferEvent { from, to, amount }),
+                Err(_) => {
+                    let fields = value(env, data)?;
+                    TokenEvent::TransferMuxed(TransferMuxedEvent {
+                        from,
+                        to,
+                        to_muxed_id: field(env, &fields, "to_muxed_id")?,
+                        amount: field(env, &fields, "amount")?,
+                    })
+                }
+            });
+        }
+        if is(symbol_short!("mint")) {
+            return Ok(TokenEvent::Mint(MintEvent {
+                to: topic(env, topics, 1)?,
+                amount: value(env, data)?,
+            }));
+        }
+        if is(symbol_short!("burn")) {
+            return Ok(TokenEvent::Burn(BurnEvent {
+                from: topic(env, topics, 1)?,
+                amount: value(env, data)?,
+            }));
+        }
+        if is(symbol_short!("approve")) {
+            let (amount, expiration_ledger): (i128, u32) = value(env, data)?;
+            return Ok(TokenEvent::Approve(ApproveEvent {
+                from: topic(env, topics, 1)?,
+                spender: topic(env, topics, 2)?,
+                amount,
+                expiration_ledger,
+            }));
+        }
+        if is(symbol_short!("clawback")) {
+            return Ok(TokenEvent::Clawback(ClawbackEvent {
+                from: topic(env, topics, 1)?,
+                amount: value(env, data)?,
+            }));
+        }
+        if is(Symbol::new(env, "set_authorized")) {
+            return Ok(TokenEvent::SetAuthorized(SetAuthorizedEvent {
+                id: topic(env, topics, 1)?,
+                authorize: value(env, data)?,
+            }));
+        }
+
+        // 3. Eventos de BDB: topics indexados + versión al final, data
+        //    en formato map (sin map, los campos faltan y fallan como
+        //    Malformed)
+        let fields: Map<Symbol, Val> = value(env, data).unwrap_or(Map::new(env));
+        let version: Result<u32, DecodeError> = topic(env, topics, topics.len().saturating_sub(1));
+        let t = |index| topic::<Address>(env, topics, index);
+        let event = if is(symbol_short!("burn_from")) {
+            TokenEvent::BurnFrom(BurnFromEvent {
+                version: version?,
+                from: t(1)?,
+                spender: t(2)?,
+                amount: field(env, &fields, "amount")?,
+            })
+        } else if is(symbol_short!("clawbk_by")) {
+            TokenEvent::ClawbackCaller(ClawbackCallerEvent {
+                version: version?,
+                caller: t(1)?,
+                from: t(2)?,
+                amount: field(env, &fields, "amount")?,
+            })
+        } else if is(symbol_short!("init")) {
+            TokenEvent::Init(InitEvent {
+                version: version?,
+                admin: t(1)?,
+                metadata: field(env, &fields, "metadata")?,
+            })
+        } else if is(symbol_short!("mint_qta")) {
+            TokenEvent::MintQuota(MintQuotaEvent {
+                version: version?,
+                minter: t(1)?,
+                to: t(2)?,
+                amount: field(env, &fields, "amount")?,
+                remaining: field(env, &fields, "remaining")?,
+            })
+        } else if is(symbol_short!("quota_set")) {
+            TokenEvent::QuotaSet(QuotaSetEvent {
+                version: version?,
+                minter: t(1)?,
+                limit: field(env, &fields, "limit")?,
+                period_ledgers: field(env, &fields, "period_ledgers")?,
+            })
+        } else if is(symbol_short!("quota_rm")) {
+            TokenEvent::QuotaRemoved(QuotaRemovedEvent { version: version?, minter: t(1)? })
+        } else if is(symbol_short!("adm_prop")) {
+            TokenEvent::AdminProposed(AdminProposedEvent {
+                version: version?,
+                current: t(1)?,
+                new_admin: t(2)?,
+                deadline_ledger: field(env, &fields, "deadline_ledger")?,
+            })
+        } else if is(symbol_short!("adm_acpt")) {
+            TokenEvent::AdminAccepted(AdminAcceptedEvent { version: version?, previous: t(1)?, new_admin: t(2)? })
+        } else if is(symbol_short!("adm_cncl")) {
+            TokenEvent::AdminProposalCancelled(AdminProposalCancelledEvent {
+                version: version?,
+                current: t(1)?,
+                proposed: t(2)?,
+            })
+        } else if is(symbol_short!("role_grnt")) {
+            TokenEvent::RoleGranted(RoleGrantedEvent {
+                version: version?,
+                role: topic(env, topics, 1)?,
+                account: t(2)?,
+                caller: field(env, &fields, "caller")?,
+            })
+        } else if is(symbol_short!("role_rvk")) {
+            TokenEvent::RoleRevoked(RoleRevokedEvent {
+                version: version?,
+                role: topic(env, topics, 1)?,
+                account: t(2)?,
+                caller: field(env, &fields, "caller")?,
+            })
+        } else if is(symbol_short!("role_rnc")) {
+            TokenEvent::RoleRenounced(RoleRenouncedEvent {
+                version: version?,
+                role: topic(env, topics, 1)?,
+                account: t(2)?,
+            })
+        } else if is(symbol_short!("paused")) {
+            TokenEvent::Paused(PausedEvent { version: version?, caller: t(1)? })
+        } else if is(symbol_short!("unpaused")) {
+            TokenEvent::Unpaused(UnpausedEvent { version: version?, caller: t(1)? })
+        } else if is(symbol_short!("max_sup")) {
+            TokenEvent::MaxSupplySet(MaxSupplySetEvent {
+                version: version?,
+                admin: t(1)?,
+                max_supply: field(env, &fields, "max_supply")?,
+            })
+        } else if is(symbol_short!("upgrade")) {
+            TokenEvent::Upgraded(UpgradedEvent {
+                version: version?,
+                admin: t(1)?,
+                new_wasm_hash: field(env, &fields, "new_wasm_hash")?,
+            })
+        } else if is(symbol_short!("migrated")) {
+            TokenEvent::Migrated(MigratedEvent {
+                version: version?,
+                from_version: field(env, &fields, "from_version")?,
+                to_version: field(env, &fields, "to_version")?,
+            })
+        } else if is(symbol_short!("batch_tx")) {
+            TokenEvent::BatchTransfer(BatchTransferEvent {
+                version: version?,
+                from: t(1)?,
+                count: field(env, &fields, "count")?,
+                total: field(env, &fields, "total")?,
+            })
+        } else if is(symbol_short!("batch_mnt")) {
+            TokenEvent::BatchMint(BatchMintEvent {
+                version: version?,
+                minter: t(1)?,
+                count: field(env, &fields, "count")?,
+                total: field(env, &fields, "total")?,
+                remaining_quota: field(env, &fields, "remaining_quota")?,
+            })
+        } else if is(symbol_short!("ckpt_hzn")) {
+            TokenEvent::CheckpointHorizon(CheckpointHorizonEvent {
+                version: version?,
+                admin: t(1)?,
+                ledger: field(env, &fields, "ledger")?,
+            })
+        } else if is(symbol_short!("delgt_chg")) {
+            TokenEvent::DelegateChanged(DelegateChangedEvent {
+                version: version?,
+                delegator: t(1)?,
+                previous: field(env, &fields, "previous")?,
+                delegatee: field(env, &fields, "delegatee")?,
+            })
+        } else if is(symbol_short!("votes_chg")) {
+            TokenEvent::VotesChanged(VotesChangedEvent {
+                version: version?,
+                delegatee: t(1)?,
+                previous: field(env, &fields, "previous")?,
+                new_votes: field(env, &fields, "new_votes")?,
+            })
+        } else if is(symbol_short!("xfer_fee")) {
+            TokenEvent::TransferFee(TransferFeeEvent {
+                version: version?,
+                from: t(1)?,
+                to: t(2)?,
+                gross: field(env, &fields, "gross")?,
+                fee: field(env, &fields, "fee")?,
+                net: field(env, &fields, "net")?,
+            })
+        } else if is(symbol_short!("fee_set")) {
+            TokenEvent::FeeSet(FeeSetEvent {
+                version: version?,
+                admin: t(1)?,
+                fee_bps: field(env, &fields, "fee_bps")?,
+                treasury: field(env, &fields, "treasury")?,
+            })
+        } else if is(symbol_short!("fee_exmpt")) {
+            TokenEvent::FeeExempt(FeeExemptEvent {
+                version: version?,
+                admin: t(1)?,
+                account: t(2)?,
+                exempt: field(env, &fields, "exempt")?,
+            })
+        } else if is(symbol_short!("rebase")) {
+            TokenEvent::Rebase(RebaseEvent {
+                version: version?,
+                caller: t(1)?,
+                old_index: field(env, &fields, "old_index")?,
+                new_index: field(env, &fields, "new_index")?,
+            })
+        } else if is(symbol_short!("meta_upd")) {
+            TokenEvent::MetadataUpdated(MetadataUpdatedEvent {
+                version: version?,
+                caller: t(1)?,
+                metadata: field(env, &fields, "metadata")?,
+            })
+        } else if is(symbol_short!("ttl_cfg")) {
+            TokenEvent::TtlConfig(TtlConfigEvent {
+                version: version?,
+                admin: t(1)?,
+                config: field(env, &fields, "config")?,
+            })
+        } else {
+            return Err(DecodeError::UnknownEvent);
+        };
+
+        // 4. Solo se decodifica la versión actual del esquema
+        if version? != EVENT_SCHEMA_VERSION {
+            return Err(DecodeError::UnsupportedVersion(version?));
+        }
+        Ok(event)
+    }
+}
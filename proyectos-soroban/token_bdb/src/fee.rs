@@ -0,0 +1,75 @@
+// src/fee.rs
+use soroban_sdk::{Address, Env};
+
+use crate::storage::{self, DataKey, FeeConfig};
+
+/// 100% en basis points
+const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Lee la configuración de fee (None = sin fee)
+pub fn read_config(env: &Env) -> Option<FeeConfig> {
+    env.storage().instance().get(&DataKey::FeeConfig)
+}
+
+/// Guarda la configuración de fee
+///
+/// Con fee_bps = 0 eliminamos la key: el camino de transfer
+/// sin fee no paga ninguna lectura extra de configuración
+pub fn write_config(env: &Env, config: &FeeConfig) {
+    if config.fee_bps == 0 {
+        env.storage().instance().remove(&DataKey::FeeConfig);
+    } else {
+        env.storage().instance().set(&DataKey::FeeConfig, config);
+    }
+}
+
+/// floor(amount * bps / 10_000) sin riesgo de overflow
+pub fn apply_bps(amount: i128, bps: u32) -> i128 {
+    let bps = bps as i128;
+    (amount / BPS_DENOMINATOR) * bps + (amount % BPS_DENOMINATOR) * bps / BPS_DENOMINATOR
+}
+
+/// Consulta si una cuenta está exenta del fee
+pub fn is_exempt(env: &Env, id: &Address) -> bool {
+    env.storage().persistent()
+        .get(&DataKey::FeeExempt(id.clone()))
+        .unwrap_or(false)
+}
+
+/// Marca o desmarca una cuenta como exenta
+///
+/// Optimización: solo las cuentas exentas ocupan storage
+pub fn write_exempt(env: &Env, id: &Address, exempt: bool) {
+    let key = DataKey::FeeExempt(id.clone());
+    if exempt {
+        env.storage().persistent().set(&key, &true);
+        storage::extend_persistent(env, &key);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Calcula el fee de una transferencia de `amount` de `from` a `to`
+///
+/// `amount` es el valor guardado que se mueve: tokens en modo
+/// estándar, shares en modo rebasing (el fee sale en la misma unidad).
+/// Devuelve (treasury, fee) o None si no corresponde cobrar:
+/// - no hay fee configurado
+/// - `from` o `to` es la treasury o una cuenta exenta (ej: pools de DEX)
+/// - el fee redondeado hacia abajo da 0
+pub fn compute(env: &Env, from: &Address, to: &Address, amount: i128) -> Option<(Address, i128)> {
+    let config = read_config(env)?;
+    if *from == config.treasury
+        || *to == config.treasury
+        || is_exempt(env, from)
+        || is_exempt(env, to)
+    {
+        return None;
+    }
+
+    let fee = apply_bps(amount, config.fee_bps);
+    if fee == 0 {
+        return None;
+    }
+    Some((config.treasury, fee))
+}
@@ -0,0 +1,106 @@
+// src/holders.rs
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::errors::TokenError;
+use crate::storage::{self, DataKey, HolderPage};
+
+/// Máximo de slots que recorre una página de holders() (y máximo `limit`)
+///
+/// Cada slot es una lectura de storage
+pub const MAX_HOLDERS_PAGE: u32 = 100;
+
+/// Cantidad de cuentas con balance distinto de 0
+pub fn read_count(env: &Env) -> u32 {
+    env.storage().instance()
+        .get(&DataKey::HolderCount)
+        .unwrap_or(0)
+}
+
+/// Cantidad de slots asignados (holders actuales + slots liberados)
+fn read_slots(env: &Env) -> u32 {
+    env.storage().instance()
+        .get(&DataKey::HolderSlots)
+        .unwrap_or(0)
+}
+
+fn read_slot(env: &Env, addr: &Address) -> Option<u32> {
+    env.storage().persistent().get(&DataKey::HolderSlot(addr.clone()))
+}
+
+/// Agrega `addr` en el próximo slot libre al final
+fn add(env: &Env, addr: &Address) {
+    let slot = read_slots(env);
+    let at_key = DataKey::HolderAt(slot);
+    let slot_key = DataKey::HolderSlot(addr.clone());
+    env.storage().persistent().set(&at_key, addr);
+    env.storage().persistent().set(&slot_key, &slot);
+    storage::extend_persistent(env, &at_key);
+    storage::extend_persistent(env, &slot_key);
+
+    env.storage().instance().set(&DataKey::HolderSlots, &(slot + 1));
+    env.storage().instance().set(&DataKey::HolderCount, &(read_count(env) + 1));
+}
+
+/// Libera el slot de `addr`
+///
+/// Borra las keys (igual que el balance en 0) en vez de mover otro
+/// holder al hueco: así ningún holder cambia de posición y las
+/// páginas ya leídas siguen valiendo
+fn remove(env: &Env, addr: &Address, slot: u32) {
+    env.storage().persistent().remove(&DataKey::HolderAt(slot));
+    env.storage().persistent().remove(&DataKey::HolderSlot(addr.clone()));
+    env.storage().instance().set(&DataKey::HolderCount, &(read_count(env) - 1));
+}
+
+/// Actualiza el registro cuando cambia el balance de `addr`
+///
+/// Se llama desde balance::write_balance: entra al pasar a un balance
+/// distinto de 0 y sale al volver a 0. Se consulta el slot en vez del
+/// balance previo para que las cuentas con balance anterior al
+/// registro (despliegues actualizados) entren en su próximo movimiento
+pub fn on_balance_change(env: &Env, addr: &Address, new_balance: i128) {
+    match (read_slot(env, addr), new_balance != 0) {
+        (None, true) => add(env, addr),
+        (Some(slot), false) => remove(env, addr, slot),
+        _ => {}
+    }
+}
+
+/// Renueva el TTL de las entradas de `addr` en el registro (si está)
+pub fn extend_ttl(env: &Env, addr: &Address) {
+    if let Some(slot) = read_slot(env, addr) {
+        storage::extend_persistent(env, &DataKey::HolderAt(slot));
+        storage::extend_persistent(env, &DataKey::HolderSlot(addr.clone()));
+    }
+}
+
+/// Página de holders: hasta `limit` holders desde el slot `start`
+///
+/// Salta los slots liberados, pero recorre como máximo
+/// MAX_HOLDERS_PAGE slots: una página puede traer menos de `limit`
+/// holders, o ninguno, aunque queden holders más adelante. El fin del
+/// registro lo marca `next` en None, no una página corta o vacía.
+/// Los holders nuevos siempre van al final y nadie cambia de slot, así
+/// que paginar con `next` no repite ni saltea cuentas aunque el registro
+/// cambie entre páginas
+pub fn page(env: &Env, start: u32, limit: u32) -> Result<HolderPage, TokenError> {
+    if limit == 0 || limit > MAX_HOLDERS_PAGE {
+        return Err(TokenError::InvalidPageLimit);
+    }
+
+    let slots = read_slots(env);
+    let scan_end = start.saturating_add(MAX_HOLDERS_PAGE).min(slots);
+    let mut holders = Vec::new(env);
+    let mut slot = start;
+    while slot < scan_end && holders.len() < limit {
+        if let Some(holder) = env.storage().persistent().get(&DataKey::HolderAt(slot)) {
+            holders.push_back(holder);
+        }
+        slot += 1;
+    }
+
+    Ok(HolderPage {
+        holders,
+        next: if slot < slots { Some(slot) } else { None },
+    })
+}
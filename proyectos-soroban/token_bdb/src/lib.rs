@@ -1,111 +1,560 @@
 // src/lib.rs
 #![no_std]
+// permit() y set_metadata() reciben más de 7 argumentos y el
+// cliente generado por #[contractimpl] hereda la firma
+#![allow(clippy::too_many_arguments)]
 
 use soroban_sdk::{
-    contract, contractimpl, Address, Env, String, 
-    symbol_short, Symbol
+    contract, contractimpl, contractmeta, log, panic_with_error, token, vec,
+    Address, Bytes, BytesN, Env, IntoVal, InvokeError, MuxedAddress, String, Symbol, Vec
 };
+// #[contractimpl] sobre `impl token::TokenInterface` genera llamadas
+// `TokenBDB::transfer(...)` que necesitan el trait en scope
+use soroban_sdk::token::TokenInterface as _;
 
+mod admin;
+mod allowance;
+mod authorization;
+mod balance;
+mod checkpoints;
+mod pause;
+mod permit;
+mod quota;
+mod rebasing;
+mod roles;
 mod storage;
 mod errors;
+pub mod events;
+mod fee;
+mod holders;
+mod metadata;
+mod upgrade;
+mod votes;
 
-use storage::{DataKey, TokenMetadata};
+#[cfg(test)]
+mod test;
+#[cfg(test)]
+mod test_sep41;
+
+use storage::{
+    DataKey, FeeConfig, HolderPage, MinterQuota, PendingAdmin, TokenMetadata, TtlConfig
+};
+// Público para que otros contratos (ej: flash_bdb) puedan pedir roles
+pub use storage::Role;
 use errors::TokenError;
+use events::{
+    AdminAcceptedEvent, AdminProposalCancelledEvent, AdminProposedEvent, ApproveEvent,
+    BatchMintEvent, BatchTransferEvent, BurnEvent, BurnFromEvent, CheckpointHorizonEvent,
+    ClawbackCallerEvent, ClawbackEvent, DelegateChangedEvent, FeeExemptEvent, FeeSetEvent,
+    InitEvent, MaxSupplySetEvent, MetadataUpdatedEvent, MintEvent, MintQuotaEvent,
+    PausedEvent, QuotaRemovedEvent, QuotaSetEvent, RebaseEvent, RoleGrantedEvent,
+    RoleRenouncedEvent, RoleRevokedEvent, SetAuthorizedEvent, TransferEvent, TransferFeeEvent,
+    TransferMuxedEvent, TtlConfigEvent, UnpausedEvent, UpgradedEvent, EVENT_SCHEMA_VERSION
+};
+
+// Versión del esquema de eventos (events::EVENT_SCHEMA_VERSION), leíble
+// desde el wasm sin invocar al contrato. Un test verifica que coincidan
+contractmeta!(key = "event_schema", val = "1");
 
 /// Constantes de configuración
 const MAX_DECIMALS: u32 = 18;
 const MAX_NAME_LENGTH: u32 = 100;
 const MAX_SYMBOL_LENGTH: u32 = 32;
+const MAX_DESCRIPTION_LENGTH: u32 = 500;
+const MAX_URI_LENGTH: u32 = 256;
+/// Largo máximo de un nombre de dominio (RFC 1035)
+const MAX_HOME_DOMAIN_LENGTH: u32 = 253;
+/// Fee de transferencia máximo (10%): ni el admin puede superarlo
+const MAX_FEE_BPS: u32 = 1_000;
+/// Destinatarios máximos por lote
+///
+/// Cada destinatario nuevo son 5 escrituras (balance, rango y entrada
+/// de checkpoint, slot e índice de holder) y uno existente 3; el resto
+/// del lote (remitente, instance y una treasury nueva) hasta 10. Con 8
+/// destinatarios nuevos y fee el lote llega a 50, el límite de
+/// escrituras por transacción de mainnet
+const MAX_BATCH: u32 = 8;
 
-/// Trait que define la interfaz del token según CAP-46
+/// Funciones administrativas del token (fuera del estándar SEP-41)
 /// 
-/// Esta es la interfaz estándar de tokens fungibles en Stellar
-/// Compatible con wallets, DEXs, y el ecosistema completo
+/// La interfaz estándar (balance, transfer, approve, burn, etc.) se
+/// implementa a través de `soroban_sdk::token::TokenInterface`, de modo
+/// que cualquier contrato puede usar `token::TokenClient` con BDB
+/// igual que con un Stellar Asset Contract.
 pub trait TokenTrait {
     /// Inicializa el token con metadatos y admin
     /// 
     /// Puede ser llamado solo una vez. Configura:
-    /// - Admin: cuenta con permisos para mintear
+    /// - Admin: dueño del contrato, recibe todos los roles
     /// - Name: nombre completo del token
     /// - Symbol: identificador corto (ej: BDB, USDC)
     /// - Decimals: precisión del token (7 para Stellar)
+    /// - Clawback: si se habilita clawback(). Solo se puede activar
+    ///   aquí; un token creado sin clawback nunca podrá tenerlo
+    /// - Max supply: tope opcional de supply total (se puede bajar
+    ///   después con set_max_supply, nunca subir)
+    /// - Rebasing: si los balances se guardan como shares de un índice
+    ///   que publica rebase() (token con rendimiento). Solo se elige
+    ///   aquí; un token estándar nunca cambia de modo
     fn initialize(
         env: Env, 
         admin: Address, 
         name: String, 
         symbol: String,
-        decimals: u32
+        decimals: u32,
+        clawback_enabled: bool,
+        max_supply: Option<i128>,
+        rebasing: bool
+    ) -> Result<(), TokenError>;
+    
+    /// Crea nuevos tokens (solo cuentas con rol Minter)
+    /// 
+    /// Aumenta el supply total y el balance del destinatario.
+    /// Si el minter tiene cuota, consume de la cuota del período.
+    /// Requiere autorización de `minter`
+    fn mint(env: Env, minter: Address, to: Address, amount: i128) -> Result<(), TokenError>;
+    
+    /// Configura la cuota de mint de un minter
+    /// 
+    /// `limit` tokens cada `period_ledgers` ledgers; la ventana
+    /// arranca en el ledger actual. Requiere que `caller` firme y
+    /// tenga el rol RoleAdmin
+    fn set_minter_quota(
+        env: Env,
+        caller: Address,
+        minter: Address,
+        limit: i128,
+        period_ledgers: u32
+    ) -> Result<(), TokenError>;
+    
+    /// Quita la cuota de un minter (queda sin límite)
+    /// 
+    /// Requiere que `caller` firme y tenga el rol RoleAdmin
+    fn remove_minter_quota(env: Env, caller: Address, minter: Address) -> Result<(), TokenError>;
+    
+    /// Otorga `role` a `account`
+    /// 
+    /// Requiere que `caller` firme y tenga el rol RoleAdmin
+    fn grant_role(env: Env, caller: Address, role: Role, account: Address) -> Result<(), TokenError>;
+    
+    /// Revoca `role` de `account`
+    /// 
+    /// Requiere que `caller` firme y tenga el rol RoleAdmin
+    fn revoke_role(env: Env, caller: Address, role: Role, account: Address) -> Result<(), TokenError>;
+    
+    /// Renuncia a un rol propio
+    /// 
+    /// Requiere autorización de `account`
+    fn renounce_role(env: Env, account: Address, role: Role) -> Result<(), TokenError>;
+    
+    /// Paso 1 del traspaso de admin: propone un nuevo admin
+    /// 
+    /// Requiere autorización del admin actual. El admin NO cambia
+    /// hasta que `new_admin` llame a accept_admin(), así un typo en
+    /// la dirección no deja el token sin admin.
+    /// `deadline_ledger` opcional: último ledger en que se puede aceptar
+    fn propose_admin(
+        env: Env,
+        new_admin: Address,
+        deadline_ledger: Option<u32>
     ) -> Result<(), TokenError>;
     
-    /// Crea nuevos tokens (solo admin)
+    /// Paso 2 del traspaso de admin: el admin propuesto acepta
+    /// 
+    /// Requiere autorización del admin propuesto
+    fn accept_admin(env: Env) -> Result<(), TokenError>;
+    
+    /// Cancela la propuesta de traspaso pendiente
+    /// 
+    /// Requiere autorización del admin actual
+    fn cancel_admin_proposal(env: Env) -> Result<(), TokenError>;
+    
+    /// Pausa el contrato: detiene transfers, mints y burns
+    /// 
+    /// Para emergencias (bug o exploit). Las consultas siguen funcionando.
+    /// Requiere que `caller` firme y tenga el rol Pauser
+    fn pause(env: Env, caller: Address) -> Result<(), TokenError>;
+    
+    /// Reanuda el contrato después de una pausa
+    /// 
+    /// Requiere que `caller` firme y tenga el rol Pauser
+    fn unpause(env: Env, caller: Address) -> Result<(), TokenError>;
+    
+    /// Congela o descongela una cuenta (como `set_authorized` del SAC)
+    /// 
+    /// Igual que en el SAC, una cuenta desautorizada no puede enviar,
+    /// recibir (tampoco mints ni el fee como treasury) ni quemar; solo
+    /// clawback la toca. Su balance sigue visible.
+    /// Requiere que `caller` firme y tenga el rol Freezer
+    fn set_authorized(env: Env, caller: Address, id: Address, authorize: bool) -> Result<(), TokenError>;
+    
+    /// Retira tokens de una cuenta sin la firma del holder
+    /// 
+    /// Reduce el balance de `from` y el supply total.
+    /// Solo disponible si se habilitó en initialize().
+    /// Requiere que `caller` firme y sea el admin o tenga el rol Clawback
+    fn clawback(env: Env, caller: Address, from: Address, amount: i128) -> Result<(), TokenError>;
+    
+    /// Baja (o define por primera vez) el tope de supply total
+    /// 
+    /// El tope solo puede bajar, nunca subir, y no puede quedar
+    /// por debajo del supply actual. Requiere autorización del admin
+    fn set_max_supply(env: Env, max_supply: i128) -> Result<(), TokenError>;
+    
+    /// Reemplaza el código del contrato por un nuevo WASM
+    /// 
+    /// El storage (balances, allowances, roles...) se conserva.
+    /// Después del upgrade hay que llamar a migrate() si el nuevo
+    /// build trae migraciones. Requiere autorización del admin
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), TokenError>;
+    
+    /// Ejecuta las migraciones de storage pendientes (N -> N+1)
+    /// 
+    /// Cada migración corre exactamente una vez. Devuelve la
+    /// versión final. Requiere autorización del admin
+    fn migrate(env: Env) -> Result<u32, TokenError>;
+    
+    /// Renueva el TTL del balance de `account`
+    /// 
+    /// Cualquiera puede llamarla (paga quien invoca), así un holder
+    /// puede evitar que su balance se archive aunque no lo mueva
+    fn extend_balance_ttl(env: Env, account: Address) -> Result<(), TokenError>;
+    
+    /// Renueva el TTL del allowance (owner, spender) hasta su expiración
+    /// 
+    /// Cualquiera puede llamarla
+    fn extend_allowance_ttl(env: Env, owner: Address, spender: Address) -> Result<(), TokenError>;
+    
+    /// Keepalive del contrato: renueva el TTL del instance storage
+    /// 
+    /// Cualquiera puede llamarla
+    fn extend_instance_ttl(env: Env) -> Result<(), TokenError>;
+    
+    /// Configura la política de TTL (umbrales y extensión)
     /// 
-    /// Aumenta el supply total y el balance del destinatario
     /// Requiere autorización del admin
-    fn mint(env: Env, to: Address, amount: i128) -> Result<(), TokenError>;
+    fn set_ttl_config(env: Env, config: TtlConfig) -> Result<(), TokenError>;
     
-    /// Destruye tokens reduciendo el supply
+    /// Transfiere a varios destinatarios en una sola operación
     /// 
-    /// Reduce el supply total y el balance del owner
-    /// Requiere autorización del owner
-    fn burn(env: Env, from: Address, amount: i128) -> Result<(), TokenError>;
+    /// Una sola autorización de `from`. El total se valida contra el
+    /// balance de `from` antes de acreditar, y cualquier error revierte
+    /// el lote completo (todo o nada). Emite un evento transfer por
+    /// destinatario más un evento resumen. El fee de transferencia
+    /// (si hay) se descuenta de lo que recibe cada destinatario.
+    /// Como máximo MAX_BATCH destinatarios (BatchTooLarge si no): cada
+    /// uno cuesta 3 escrituras, o 5 si aún no tiene BDB, y el límite
+    /// de mainnet es de 50 escrituras por transacción
+    fn batch_transfer(
+        env: Env,
+        from: Address,
+        recipients: Vec<(Address, i128)>
+    ) -> Result<(), TokenError>;
     
-    /// Consulta el balance de una cuenta
+    /// Mintea a varios destinatarios en una sola operación
     /// 
-    /// Devuelve 0 si la cuenta nunca ha recibido tokens
-    fn balance(env: Env, account: Address) -> i128;
+    /// Pensado para distribuciones iniciales. Igual que mint(): requiere
+    /// la firma de `minter` con rol Minter y consume su cuota por el
+    /// total del lote. Una sola actualización del supply total; el tope
+    /// de supply y la pausa se aplican igual que en mint().
+    /// Como máximo MAX_BATCH destinatarios. Cualquier entrada inválida
+    /// revierte el lote completo; first_invalid_mint_entry() dice cuál
+    /// (el índice también queda en el log de diagnóstico)
+    fn batch_mint(
+        env: Env,
+        minter: Address,
+        recipients: Vec<(Address, i128)>
+    ) -> Result<(), TokenError>;
     
-    /// Transfiere tokens entre cuentas
+    /// Índice de la primera entrada que haría fallar batch_mint
     /// 
-    /// Requiere autorización de `from`
-    /// No permite transferencias a sí mismo
-    fn transfer(
-        env: Env, 
-        from: Address, 
-        to: Address, 
-        amount: i128
+    /// Solo lectura: aplica a cada entrada la misma validación que
+    /// batch_mint (monto positivo, al menos una share, destinatario
+    /// no congelado). None si todas son válidas. No revisa lo que
+    /// depende del lote completo (tamaño, cuota, tope de supply)
+    fn first_invalid_mint_entry(env: Env, recipients: Vec<(Address, i128)>) -> Option<u32>;
+    
+    /// Balance de `account` al cierre de `ledger` (snapshots de gobernanza)
+    /// 
+    /// Falla con FutureLedger si el ledger aún no cerró (incluido el
+    /// actual) y con HistoryPruned si es anterior al horizonte de
+    /// checkpoints
+    fn balance_at(env: Env, account: Address, ledger: u32) -> Result<i128, TokenError>;
+    
+    /// Supply total al cierre de `ledger`
+    /// 
+    /// Mismas reglas que balance_at
+    fn total_supply_at(env: Env, ledger: u32) -> Result<i128, TokenError>;
+    
+    /// Sube el horizonte del historial y poda el historial del supply
+    /// (hasta MAX_PRUNE_PER_CALL checkpoints; el resto se poda con las
+    /// próximas escrituras)
+    /// 
+    /// Las consultas anteriores a `ledger` dejan de estar disponibles.
+    /// El horizonte solo puede subir. Requiere autorización del admin
+    fn set_checkpoint_horizon(env: Env, ledger: u32) -> Result<(), TokenError>;
+    
+    /// Elimina los checkpoints de balance y de votos de `account`
+    /// anteriores al horizonte
+    /// 
+    /// Cualquiera puede llamarla (también se poda un checkpoint con
+    /// cada escritura). Borra como máximo MAX_PRUNE_PER_CALL por
+    /// historial: si devuelve ese máximo puede quedar más para podar.
+    /// Devuelve cuántos checkpoints se eliminaron
+    fn prune_checkpoints(env: Env, account: Address) -> Result<u32, TokenError>;
+    
+    /// Delega el poder de voto de `delegator` en `delegatee`
+    /// 
+    /// El balance solo cuenta como votos una vez delegado (para votar
+    /// con el propio balance: delegate(yo, yo)). Desde ahí los votos
+    /// siguen automáticamente a transfer, mint y burn.
+    /// Requiere autorización de `delegator`
+    fn delegate(env: Env, delegator: Address, delegatee: Address) -> Result<(), TokenError>;
+    
+    /// Aprueba a `spender` con una firma off-chain del owner
+    /// 
+    /// Para wallets que firman mensajes pero no envían transacciones:
+    /// cualquiera puede enviar el permit. La firma ed25519 de
+    /// `public_key` (la clave de la cuenta G... `owner`) debe cubrir el
+    /// XDR de un PermitPayload (contrato, hash de la red, owner,
+    /// spender, amount, expiration_ledger, nonce, deadline_ledger).
+    /// `nonce` debe ser el próximo nonce del owner y la firma vence
+    /// después de `deadline_ledger`. Una firma inválida aborta la
+    /// invocación
+    fn permit(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+        nonce: u64,
+        deadline_ledger: u32,
+        public_key: BytesN<32>,
+        signature: BytesN<64>
     ) -> Result<(), TokenError>;
     
-    /// Aprueba a otro usuario para gastar tokens
+    /// Configura el fee de transferencia
     /// 
-    /// Permite que `spender` gaste hasta `amount` tokens
-    /// de la cuenta de `from`. Se puede revocar con amount=0
-    fn approve(
-        env: Env, 
-        from: Address, 
-        spender: Address, 
-        amount: i128
+    /// `fee_bps` en basis points (100 = 1%), nunca mayor a MAX_FEE_BPS;
+    /// 0 desactiva el fee. Se cobra en transfer, transfer_from y
+    /// batch_transfer y va a `treasury`. Requiere autorización del admin
+    fn set_transfer_fee(env: Env, fee_bps: u32, treasury: Address) -> Result<(), TokenError>;
+    
+    /// Agrega o quita una cuenta de la lista de exentos del fee
+    /// 
+    /// Una transferencia no paga fee si el origen o el destino está
+    /// exento (ej: pools de DEX). La treasury siempre está exenta.
+    /// Requiere autorización del admin
+    fn set_fee_exempt(env: Env, account: Address, exempt: bool) -> Result<(), TokenError>;
+    
+    /// Transfiere a un contrato y le avisa en la misma transacción
+    /// 
+    /// Mueve los tokens y luego invoca
+    /// `on_token_received(token, from, amount, data)` en `to_contract`
+    /// con el monto recibido (neto de fee). Si el receptor devuelve
+    /// error o no implementa el hook se revierte todo, incluida la
+    /// transferencia. Requiere autorización de `from`
+    fn transfer_and_call(
+        env: Env,
+        from: Address,
+        to_contract: Address,
+        amount: i128,
+        data: Bytes
     ) -> Result<(), TokenError>;
     
-    /// Consulta el allowance entre dos cuentas
+    /// Publica un nuevo índice de rebase (solo tokens rebasing)
     /// 
-    /// Devuelve cuánto puede gastar `spender` de los tokens de `from`
-    fn allowance(env: Env, from: Address, spender: Address) -> i128;
+    /// Los balances pasan a valer shares × new_index / INDEX_SCALE:
+    /// un índice mayor reparte rendimiento a todos los holders sin
+    /// escribir cada balance. Requiere que `caller` firme y sea el
+    /// admin o tenga el rol Oracle
+    fn rebase(env: Env, caller: Address, new_index: i128) -> Result<(), TokenError>;
     
-    /// Transfiere tokens en nombre de otro usuario
+    /// Actualiza la metadata del token
     /// 
-    /// Requiere allowance previo mediante approve()
-    /// Reduce el allowance automáticamente
-    fn transfer_from(
-        env: Env, 
-        spender: Address, 
-        from: Address, 
-        to: Address, 
-        amount: i128
+    /// Reemplaza name, symbol y los campos opcionales (None borra el
+    /// campo); decimals no cambia. Mismas validaciones de largo que
+    /// initialize(). Requiere que `caller` firme y sea el admin o tenga
+    /// el rol MetadataManager
+    fn set_metadata(
+        env: Env,
+        caller: Address,
+        name: String,
+        symbol: String,
+        description: Option<String>,
+        image_uri: Option<String>,
+        home_domain: Option<String>,
+        website: Option<String>
     ) -> Result<(), TokenError>;
     
+    /// Votos actuales de `account`
+    fn get_votes(env: Env, account: Address) -> i128;
+    
+    /// Votos de `account` al cierre de `ledger`
+    /// 
+    /// Mismas reglas que balance_at
+    fn get_past_votes(env: Env, account: Address, ledger: u32) -> Result<i128, TokenError>;
+    
     // Métodos de consulta (getters)
-    fn name(env: Env) -> String;
-    fn symbol(env: Env) -> String;
-    fn decimals(env: Env) -> u32;
     fn total_supply(env: Env) -> i128;
+    fn metadata(env: Env) -> Result<TokenMetadata, TokenError>;
     fn admin(env: Env) -> Address;
+    fn pending_admin(env: Env) -> Option<PendingAdmin>;
+    fn has_role(env: Env, role: Role, account: Address) -> bool;
+    fn is_paused(env: Env) -> bool;
+    fn authorized(env: Env, id: Address) -> bool;
+    fn clawback_enabled(env: Env) -> bool;
+    fn max_supply(env: Env) -> Option<i128>;
+    fn minter_quota(env: Env, minter: Address) -> Option<MinterQuota>;
+    fn schema_version(env: Env) -> u32;
+    fn ttl_config(env: Env) -> TtlConfig;
+    fn checkpoint_horizon(env: Env) -> u32;
+    fn delegates(env: Env, account: Address) -> Option<Address>;
+    fn nonce(env: Env, owner: Address) -> u64;
+    fn transfer_fee(env: Env) -> Option<FeeConfig>;
+    fn is_fee_exempt(env: Env, account: Address) -> bool;
+    fn is_rebasing(env: Env) -> bool;
+    fn rebase_index(env: Env) -> i128;
+    
+    /// Shares de `account` (igual al balance en modo estándar)
+    /// 
+    /// Los votos delegados se cuentan en shares
+    fn shares_of(env: Env, account: Address) -> i128;
+    
+    /// Total de shares emitidas (igual al supply en modo estándar)
+    fn total_shares(env: Env) -> i128;
+    
+    /// Cantidad de cuentas con balance distinto de 0
+    /// 
+    /// Las cuentas con balance anterior al registro (despliegues
+    /// actualizados) se cuentan desde su próximo movimiento
+    fn holder_count(env: Env) -> u32;
+    
+    /// Lista hasta `limit` holders desde el slot `start`
+    /// 
+    /// Paginación estable: los holders nuevos van al final y nadie
+    /// cambia de posición, así que seguir `next` no repite ni saltea
+    /// cuentas aunque el registro cambie entre páginas. Los slots de
+    /// cuentas que salieron quedan vacíos y se saltean, pero una página
+    /// lee como máximo MAX_HOLDERS_PAGE slots: puede traer menos de
+    /// `limit` holders o venir vacía aunque queden holders. Hay que
+    /// seguir `next` hasta que sea None (holder_count() da el total).
+    /// `limit` entre 1 y MAX_HOLDERS_PAGE
+    fn holders(env: Env, start: u32, limit: u32) -> Result<HolderPage, TokenError>;
 }
 
 /// Estructura del contrato Token BDB
 #[contract]
 pub struct TokenBDB;
 
-/// Implementación del contrato
+/// Verifica que el contrato esté inicializado
+/// 
+/// Las funciones de SEP-41 no devuelven Result, así que los errores
+/// se reportan con panic_with_error! (el código sigue siendo TokenError)
+fn require_initialized(env: &Env) {
+    if !env.storage().instance().has(&DataKey::Initialized) {
+        panic_with_error!(env, TokenError::NotInitialized);
+    }
+}
+
+/// Verifica que el contrato no esté pausado
+fn require_not_paused(env: &Env) {
+    if pause::is_paused(env) {
+        panic_with_error!(env, TokenError::ContractPaused);
+    }
+}
+
+/// Verifica que la cuenta no esté congelada (set_authorized = false)
+fn require_authorized(env: &Env, id: &Address) {
+    if !authorization::is_authorized(env, id) {
+        panic_with_error!(env, TokenError::AccountNotAuthorized);
+    }
+}
+
+/// Valida que el amount sea estrictamente positivo
+fn require_positive(env: &Env, amount: i128) {
+    if amount <= 0 {
+        panic_with_error!(env, TokenError::InvalidAmount);
+    }
+}
+
+/// Desenvuelve un Result interno o aborta con el TokenError correspondiente
+fn unwrap_or_panic<T>(env: &Env, result: Result<T, TokenError>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(error) => panic_with_error!(env, error),
+    }
+}
+
+/// Valida una entrada de batch_mint y devuelve sus shares
+/// 
+/// Monto positivo que llegue a una share y destinatario no congelado.
+/// La usan batch_mint y first_invalid_mint_entry, así la consulta
+/// nunca se aparta de lo que rechaza el lote
+fn validate_mint_entry(env: &Env, to: &Address, amount: i128) -> Result<i128, TokenError> {
+    if amount <= 0 {
+        return Err(TokenError::InvalidAmount);
+    }
+    let shares = rebasing::to_shares(env, amount);
+    if shares == 0 {
+        return Err(TokenError::InvalidAmount);
+    }
+    authorization::ensure_authorized(env, to)?;
+    Ok(shares)
+}
+
+/// Acredita una transferencia de `amount` ya debitada de `from`
+/// 
+/// `shares` son las que devolvió spend_balance (= `amount` en modo
+/// estándar). Si corresponde fee se calcula sobre esas shares y `to`
+/// recibe el resto: from pierde exactamente lo que reciben to y la
+/// treasury, sin redondear dos veces.
+/// Eventos:
+/// - transfer de from a to con el neto (muxed si corresponde)
+/// - con fee: transfer de from a la treasury con el fee y
+///   xfer_fee con (gross, fee, net)
+/// 
+/// Devuelve el neto que recibió `to` (en tokens)
+fn credit_transfer(
+    env: &Env,
+    from: &Address,
+    to: &Address,
+    to_muxed_id: Option<u64>,
+    amount: i128,
+    shares: i128
+) -> Result<i128, TokenError> {
+    let fee = fee::compute(env, from, to, shares);
+    let fee_shares = fee.as_ref().map_or(0, |(_, fee_shares)| *fee_shares);
+    let fee_amount = rebasing::to_amount(env, fee_shares);
+    let net = amount - fee_amount;
+    
+    balance::receive_shares(env, to, shares - fee_shares)?;
+    match to_muxed_id {
+        Some(to_muxed_id) => {
+            TransferMuxedEvent { from: from.clone(), to: to.clone(), to_muxed_id, amount: net }.publish(env)
+        }
+        None => TransferEvent { from: from.clone(), to: to.clone(), amount: net }.publish(env),
+    }
+    
+    if let Some((treasury, fee_shares)) = fee {
+        // La treasury recibe como cualquier destino: congelada no cobra
+        authorization::ensure_authorized(env, &treasury)?;
+        balance::receive_shares(env, &treasury, fee_shares)?;
+        TransferEvent { from: from.clone(), to: treasury, amount: fee_amount }.publish(env);
+        TransferFeeEvent {
+            from: from.clone(),
+            to: to.clone(),
+            gross: amount,
+            fee: fee_amount,
+            net,
+            version: EVENT_SCHEMA_VERSION,
+        }.publish(env);
+    }
+    
+    Ok(net)
+}
+
+/// Implementación de las funciones administrativas
 #[contractimpl]
 impl TokenTrait for TokenBDB {
     fn initialize(
@@ -113,7 +562,10 @@ impl TokenTrait for TokenBDB {
         admin: Address, 
         name: String, 
         symbol: String,
-        decimals: u32
+        decimals: u32,
+        clawback_enabled: bool,
+        max_supply: Option<i128>,
+        rebasing: bool
     ) -> Result<(), TokenError> {
         // 1. Verificar que no esté inicializado
         if env.storage().instance().has(&DataKey::Initialized) {
@@ -126,14 +578,14 @@ impl TokenTrait for TokenBDB {
         }
         
         // 3. Validar metadatos (name y symbol no vacíos)
-        // Nota: String en Soroban no tiene .len() directo,
-        // pero podemos convertir a bytes para validar
-        if name.len() == 0 || name.len() > MAX_NAME_LENGTH {
-            return Err(TokenError::InvalidMetadata);
-        }
+        metadata::validate_name_symbol(&name, &symbol)?;
         
-        if symbol.len() == 0 || symbol.len() > MAX_SYMBOL_LENGTH {
-            return Err(TokenError::InvalidMetadata);
+        // 3b. Validar el tope de supply (si se definió)
+        if let Some(cap) = max_supply {
+            if cap <= 0 {
+                return Err(TokenError::InvalidSupplyCap);
+            }
+            env.storage().instance().set(&DataKey::MaxSupply, &cap);
         }
         
         // 4. Guardar metadata en instance storage
@@ -142,398 +594,854 @@ impl TokenTrait for TokenBDB {
         env.storage().instance().set(&DataKey::TokenSymbol, &symbol);
         env.storage().instance().set(&DataKey::Decimals, &decimals);
         env.storage().instance().set(&DataKey::TotalSupply, &0i128);
+        env.storage().instance().set(&DataKey::ClawbackEnabled, &clawback_enabled);
+        if rebasing {
+            env.storage().instance().set(&DataKey::Rebasing, &true);
+            env.storage().instance().set(&DataKey::RebaseIndex, &rebasing::INDEX_SCALE);
+        }
         env.storage().instance().set(&DataKey::Initialized, &true);
+        upgrade::write_schema_version(&env, upgrade::CURRENT_SCHEMA_VERSION);
+        
+        // 4b. El admin arranca con todos los roles (luego puede delegarlos)
+        for role in roles::ALL_ROLES {
+            roles::grant(&env, role, &admin);
+        }
         
-        // 5. Extender TTL del storage de instance (30 días)
-        env.storage().instance().extend_ttl(100_000, 200_000);
+        // 5. Extender TTL del storage de instance (política por defecto)
+        storage::extend_instance(&env);
         
         // 6. Emitir evento rico con todos los metadatos
-        env.events().publish(
-            (symbol_short!("init"), admin.clone()),
-            TokenMetadata {
-                name: name.clone(),
-                symbol: symbol.clone(),
-                decimals,
-            }
-        );
+        InitEvent {
+            admin,
+            metadata: metadata::read(&env),
+            version: EVENT_SCHEMA_VERSION,
+        }.publish(&env);
         
         Ok(())
     }
     
-    fn mint(env: Env, to: Address, amount: i128) -> Result<(), TokenError> {
+    fn mint(env: Env, minter: Address, to: Address, amount: i128) -> Result<(), TokenError> {
         // 1. Verificar inicialización
         if !env.storage().instance().has(&DataKey::Initialized) {
             return Err(TokenError::NotInitialized);
         }
         
-        // 2. Solo el admin puede mintear
-        let admin: Address = env.storage().instance()
-            .get(&DataKey::Admin)
-            .ok_or(TokenError::NotInitialized)?;
-        admin.require_auth();
+        // 2. No se mintea con el contrato pausado
+        pause::ensure_not_paused(&env)?;
         
-        // 3. Validaciones
+        // 3. Solo cuentas con rol Minter pueden mintear
+        roles::require_role(&env, Role::Minter, &minter)?;
+        
+        // 4. Validaciones (una cuenta congelada no recibe mints)
         if amount <= 0 {
             return Err(TokenError::InvalidAmount);
         }
+        authorization::ensure_authorized(&env, &to)?;
         
-        // 4. Validar que `to` no sea igual a `admin` (opcional, pero buena práctica)
-        // Esto evita que el admin se mintee tokens a sí mismo por error
+        // 5. Consumir cuota del minter (si tiene una configurada)
+        let remaining_quota = quota::consume(&env, &minter, amount)?;
         
-        // 5. Obtener balance actual y verificar overflow
-        let balance = Self::balance(env.clone(), to.clone());
-        let new_balance = balance.checked_add(amount)
-            .ok_or(TokenError::OverflowError)?;
-        
-        // 6. Actualizar balance con TTL extendido
-        env.storage().persistent().set(
-            &DataKey::Balance(to.clone()), 
-            &new_balance
-        );
-        env.storage().persistent().extend_ttl(
-            &DataKey::Balance(to.clone()),
-            100_000,
-            200_000
-        );
+        // 6. Actualizar balance y total supply con verificación de overflow
+        balance::receive_balance(&env, &to, amount)?;
+        balance::increase_supply(&env, amount)?;
         
-        // 7. Actualizar total supply
-        let total: i128 = env.storage().instance()
-            .get(&DataKey::TotalSupply)
-            .unwrap_or(0);
-        let new_total = total.checked_add(amount)
-            .ok_or(TokenError::OverflowError)?;
-        env.storage().instance().set(
-            &DataKey::TotalSupply, 
-            &new_total
-        );
+        // 7. Emitir evento estándar de mint
+        MintEvent { to: to.clone(), amount }.publish(&env);
         
-        // 8. Emitir evento detallado
-        env.events().publish(
-            (symbol_short!("mint"), to.clone()), 
-            (amount, new_balance, new_total)
-        );
+        // 8. Si hay cuota, informar cuánto le queda al minter en el período
+        if let Some(remaining) = remaining_quota {
+            MintQuotaEvent {
+                minter,
+                to,
+                amount,
+                remaining,
+                version: EVENT_SCHEMA_VERSION,
+            }.publish(&env);
+        }
         
         Ok(())
     }
     
-    fn burn(env: Env, from: Address, amount: i128) -> Result<(), TokenError> {
+    fn set_minter_quota(
+        env: Env,
+        caller: Address,
+        minter: Address,
+        limit: i128,
+        period_ledgers: u32
+    ) -> Result<(), TokenError> {
         // 1. Verificar inicialización
         if !env.storage().instance().has(&DataKey::Initialized) {
             return Err(TokenError::NotInitialized);
         }
         
-        // 2. Requiere autorización del dueño de los tokens
-        from.require_auth();
+        // 2. Solo RoleAdmin configura cuotas
+        roles::require_role(&env, Role::RoleAdmin, &caller)?;
         
         // 3. Validaciones
-        if amount <= 0 {
-            return Err(TokenError::InvalidAmount);
+        if limit < 0 || period_ledgers == 0 {
+            return Err(TokenError::InvalidQuota);
         }
         
-        let balance = Self::balance(env.clone(), from.clone());
-        if balance < amount {
-            return Err(TokenError::InsufficientBalance);
-        }
-        
-        // 4. Actualizar balance
-        let new_balance = balance - amount;
-        if new_balance == 0 {
-            // Optimización: eliminar key si balance = 0
-            env.storage().persistent().remove(&DataKey::Balance(from.clone()));
-        } else {
-            env.storage().persistent().set(
-                &DataKey::Balance(from.clone()),
-                &new_balance
-            );
-            env.storage().persistent().extend_ttl(
-                &DataKey::Balance(from.clone()),
-                100_000,
-                200_000
-            );
-        }
-        
-        // 5. Actualizar total supply
-        let total: i128 = env.storage().instance()
-            .get(&DataKey::TotalSupply)
-            .unwrap_or(0);
-        let new_total = total.checked_sub(amount)
-            .ok_or(TokenError::OverflowError)?;
-        env.storage().instance().set(
-            &DataKey::TotalSupply,
-            &new_total
-        );
+        // 4. Guardar cuota con la ventana arrancando ahora
+        let quota = MinterQuota {
+            limit,
+            period_ledgers,
+            window_start: env.ledger().sequence(),
+            used: 0,
+        };
+        quota::write_quota(&env, &minter, &quota);
         
-        // 6. Emitir evento
-        env.events().publish(
-            (symbol_short!("burn"), from),
-            (amount, new_balance, new_total)
-        );
+        // 5. Emitir evento
+        QuotaSetEvent {
+            minter,
+            limit,
+            period_ledgers,
+            version: EVENT_SCHEMA_VERSION,
+        }.publish(&env);
         
         Ok(())
     }
     
-    fn balance(env: Env, account: Address) -> i128 {
-        env.storage().persistent()
-            .get(&DataKey::Balance(account))
-            .unwrap_or(0)
-    }
-    
-    fn transfer(
-        env: Env, 
-        from: Address, 
-        to: Address, 
-        amount: i128
-    ) -> Result<(), TokenError> {
+    fn remove_minter_quota(env: Env, caller: Address, minter: Address) -> Result<(), TokenError> {
         // 1. Verificar inicialización
         if !env.storage().instance().has(&DataKey::Initialized) {
             return Err(TokenError::NotInitialized);
         }
         
-        // 2. Verificar autorización del sender
-        from.require_auth();
+        // 2. Solo RoleAdmin configura cuotas
+        roles::require_role(&env, Role::RoleAdmin, &caller)?;
         
-        // 3. Validaciones
-        if amount <= 0 {
-            return Err(TokenError::InvalidAmount);
-        }
+        // 3. Eliminar cuota y emitir evento
+        quota::remove_quota(&env, &minter);
+        QuotaRemovedEvent { minter, version: EVENT_SCHEMA_VERSION }.publish(&env);
         
-        // 4. No permitir transferencia a sí mismo (gas-efficient)
-        if from == to {
-            return Err(TokenError::InvalidRecipient);
+        Ok(())
+    }
+    
+    fn propose_admin(
+        env: Env,
+        new_admin: Address,
+        deadline_ledger: Option<u32>
+    ) -> Result<(), TokenError> {
+        // 1. Solo el admin actual puede proponer
+        let current = admin::require_admin(&env)?;
+        
+        // 2. Un deadline en el pasado nunca podría aceptarse
+        if let Some(deadline) = deadline_ledger {
+            if deadline < env.ledger().sequence() {
+                return Err(TokenError::AdminProposalExpired);
+            }
         }
         
-        let from_balance = Self::balance(env.clone(), from.clone());
-        if from_balance < amount {
-            return Err(TokenError::InsufficientBalance);
-        }
-        
-        // 5. Calcular nuevos balances con verificación de overflow
-        let new_from_balance = from_balance - amount;
-        let to_balance = Self::balance(env.clone(), to.clone());
-        let new_to_balance = to_balance.checked_add(amount)
-            .ok_or(TokenError::OverflowError)?;
-        
-        // 6. Actualizar balances con TTL
-        // Optimización: si from_balance = 0, eliminar key
-        if new_from_balance == 0 {
-            env.storage().persistent().remove(&DataKey::Balance(from.clone()));
-        } else {
-            env.storage().persistent().set(
-                &DataKey::Balance(from.clone()),
-                &new_from_balance
-            );
-            env.storage().persistent().extend_ttl(
-                &DataKey::Balance(from.clone()),
-                100_000,
-                200_000
-            );
-        }
-        
-        env.storage().persistent().set(
-            &DataKey::Balance(to.clone()),
-            &new_to_balance
-        );
-        env.storage().persistent().extend_ttl(
-            &DataKey::Balance(to.clone()),
-            100_000,
-            200_000
-        );
+        // 3. Guardar propuesta (reemplaza cualquier propuesta anterior)
+        admin::write_pending_admin(&env, &PendingAdmin {
+            admin: new_admin.clone(),
+            deadline_ledger,
+        });
         
-        // 7. Emitir evento con balances post-transferencia
-        env.events().publish(
-            (symbol_short!("transfer"), from, to), 
-            (amount, new_from_balance, new_to_balance)
-        );
+        // 4. Emitir evento
+        AdminProposedEvent {
+            current,
+            new_admin,
+            deadline_ledger,
+            version: EVENT_SCHEMA_VERSION,
+        }.publish(&env);
         
         Ok(())
     }
     
-    fn approve(
-        env: Env, 
-        from: Address, 
-        spender: Address, 
-        amount: i128
-    ) -> Result<(), TokenError> {
-        // 1. Verificar inicialización
-        if !env.storage().instance().has(&DataKey::Initialized) {
-            return Err(TokenError::NotInitialized);
+    fn accept_admin(env: Env) -> Result<(), TokenError> {
+        // 1. Debe existir una propuesta
+        let pending = admin::read_pending_admin(&env)
+            .ok_or(TokenError::NoPendingAdmin)?;
+        
+        // 2. Solo el admin propuesto puede aceptar
+        pending.admin.require_auth();
+        
+        // 3. Verificar que la propuesta no haya vencido
+        if let Some(deadline) = pending.deadline_ledger {
+            if env.ledger().sequence() > deadline {
+                return Err(TokenError::AdminProposalExpired);
+            }
         }
         
-        // 2. Verificar autorización del owner
-        from.require_auth();
+        // 4. Aplicar el traspaso
+        let previous = admin::read_admin(&env)?;
+        admin::write_admin(&env, &pending.admin);
+        admin::remove_pending_admin(&env);
         
-        // 3. Validación: amount debe ser >= 0 (permitir 0 para revocar)
-        if amount < 0 {
-            return Err(TokenError::InvalidAmount);
+        // 5. Los roles del admin anterior pasan al nuevo: la cuenta
+        //    anterior no puede seguir minteando, pausando ni otorgando roles
+        for role in roles::transfer_all(&env, &previous, &pending.admin).iter() {
+            RoleRevokedEvent {
+                role,
+                account: previous.clone(),
+                caller: pending.admin.clone(),
+                version: EVENT_SCHEMA_VERSION,
+            }.publish(&env);
+            RoleGrantedEvent {
+                role,
+                account: pending.admin.clone(),
+                caller: pending.admin.clone(),
+                version: EVENT_SCHEMA_VERSION,
+            }.publish(&env);
         }
         
-        // 4. Obtener allowance anterior para el evento
-        let old_allowance = Self::allowance(env.clone(), from.clone(), spender.clone());
-        
-        // 5. Actualizar allowance
-        if amount == 0 {
-            // Optimización: eliminar key si allowance = 0
-            env.storage().persistent().remove(
-                &DataKey::Allowance(from.clone(), spender.clone())
-            );
-        } else {
-            env.storage().persistent().set(
-                &DataKey::Allowance(from.clone(), spender.clone()),
-                &amount
-            );
-            env.storage().persistent().extend_ttl(
-                &DataKey::Allowance(from.clone(), spender.clone()),
-                100_000,
-                200_000
-            );
-        }
-        
-        // 6. Evento mejorado con allowance anterior y nuevo
-        env.events().publish(
-            (symbol_short!("approve"), from, spender),
-            (old_allowance, amount)
-        );
+        // 6. Emitir evento
+        AdminAcceptedEvent {
+            previous,
+            new_admin: pending.admin,
+            version: EVENT_SCHEMA_VERSION,
+        }.publish(&env);
         
         Ok(())
     }
     
-    fn allowance(env: Env, from: Address, spender: Address) -> i128 {
-        env.storage().persistent()
-            .get(&DataKey::Allowance(from, spender))
-            .unwrap_or(0)
+    fn cancel_admin_proposal(env: Env) -> Result<(), TokenError> {
+        // 1. Solo el admin actual puede cancelar
+        let current = admin::require_admin(&env)?;
+        
+        // 2. Debe existir una propuesta
+        let pending = admin::read_pending_admin(&env)
+            .ok_or(TokenError::NoPendingAdmin)?;
+        admin::remove_pending_admin(&env);
+        
+        // 3. Emitir evento
+        AdminProposalCancelledEvent {
+            current,
+            proposed: pending.admin,
+            version: EVENT_SCHEMA_VERSION,
+        }.publish(&env);
+        
+        Ok(())
     }
     
-    fn transfer_from(
-        env: Env, 
-        spender: Address, 
-        from: Address, 
-        to: Address, 
-        amount: i128
-    ) -> Result<(), TokenError> {
+    fn grant_role(env: Env, caller: Address, role: Role, account: Address) -> Result<(), TokenError> {
         // 1. Verificar inicialización
         if !env.storage().instance().has(&DataKey::Initialized) {
             return Err(TokenError::NotInitialized);
         }
         
-        // 2. Verificar autorización del spender
-        spender.require_auth();
+        // 2. Solo RoleAdmin puede otorgar roles
+        roles::require_role(&env, Role::RoleAdmin, &caller)?;
         
-        // 3. Validaciones
-        if amount <= 0 {
-            return Err(TokenError::InvalidAmount);
-        }
+        // 3. Guardar rol y emitir evento
+        roles::grant(&env, role, &account);
+        RoleGrantedEvent { role, account, caller, version: EVENT_SCHEMA_VERSION }.publish(&env);
         
-        // 4. No permitir transferencia a sí mismo
-        if from == to {
-            return Err(TokenError::InvalidRecipient);
+        Ok(())
+    }
+    
+    fn revoke_role(env: Env, caller: Address, role: Role, account: Address) -> Result<(), TokenError> {
+        // 1. Verificar inicialización
+        if !env.storage().instance().has(&DataKey::Initialized) {
+            return Err(TokenError::NotInitialized);
         }
         
-        // 5. Verificar allowance
-        let allowed = Self::allowance(env.clone(), from.clone(), spender.clone());
-        if allowed < amount {
-            return Err(TokenError::InsufficientAllowance);
-        }
-        
-        // 6. Verificar balance
-        let from_balance = Self::balance(env.clone(), from.clone());
-        if from_balance < amount {
-            return Err(TokenError::InsufficientBalance);
-        }
-        
-        // 7. Calcular nuevos valores
-        let new_from_balance = from_balance - amount;
-        let to_balance = Self::balance(env.clone(), to.clone());
-        let new_to_balance = to_balance.checked_add(amount)
-            .ok_or(TokenError::OverflowError)?;
-        let new_allowance = allowed - amount;
-        
-        // 8. Actualizar estado atómicamente
-        // Optimización: eliminar keys si son 0
-        if new_from_balance == 0 {
-            env.storage().persistent().remove(&DataKey::Balance(from.clone()));
-        } else {
-            env.storage().persistent().set(
-                &DataKey::Balance(from.clone()),
-                &new_from_balance
-            );
-            env.storage().persistent().extend_ttl(
-                &DataKey::Balance(from.clone()),
-                100_000,
-                200_000
-            );
-        }
-        
-        env.storage().persistent().set(
-            &DataKey::Balance(to.clone()),
-            &new_to_balance
-        );
-        env.storage().persistent().extend_ttl(
-            &DataKey::Balance(to.clone()),
-            100_000,
-            200_000
-        );
+        // 2. Solo RoleAdmin puede revocar roles
+        roles::require_role(&env, Role::RoleAdmin, &caller)?;
         
-        if new_allowance == 0 {
-            env.storage().persistent().remove(
-                &DataKey::Allowance(from.clone(), spender.clone())
-            );
-        } else {
-            env.storage().persistent().set(
-                &DataKey::Allowance(from.clone(), spender.clone()),
-                &new_allowance
-            );
-            env.storage().persistent().extend_ttl(
-                &DataKey::Allowance(from.clone(), spender.clone()),
-                100_000,
-                200_000
-            );
-        }
-        
-        // 9. Emitir evento completo (FIX: evento faltante)
-        env.events().publish(
-            (symbol_short!("trnsfr_frm"), spender, from.clone(), to.clone()),
-            (amount, new_from_balance, new_to_balance, new_allowance)
-        );
+        // 3. Eliminar rol y emitir evento
+        roles::revoke(&env, role, &account);
+        RoleRevokedEvent { role, account, caller, version: EVENT_SCHEMA_VERSION }.publish(&env);
         
         Ok(())
     }
     
-    // Métodos de consulta
-    fn name(env: Env) -> String {
-        // Verificar inicialización antes de devolver metadata
+    fn renounce_role(env: Env, account: Address, role: Role) -> Result<(), TokenError> {
+        // 1. Solo la propia cuenta puede renunciar
+        account.require_auth();
+        
+        // 2. Eliminar rol y emitir evento
+        roles::revoke(&env, role, &account);
+        RoleRenouncedEvent { role, account, version: EVENT_SCHEMA_VERSION }.publish(&env);
+        
+        Ok(())
+    }
+    
+    fn pause(env: Env, caller: Address) -> Result<(), TokenError> {
+        // 1. Verificar inicialización
         if !env.storage().instance().has(&DataKey::Initialized) {
-            return String::from_str(&env, "");
+            return Err(TokenError::NotInitialized);
         }
         
-        env.storage().instance()
-            .get(&DataKey::TokenName)
-            .unwrap_or(String::from_str(&env, ""))
+        // 2. Solo cuentas con rol Pauser
+        roles::require_role(&env, Role::Pauser, &caller)?;
+        
+        // 3. Activar pausa y emitir evento
+        pause::set_paused(&env, true);
+        PausedEvent { caller, version: EVENT_SCHEMA_VERSION }.publish(&env);
+        
+        Ok(())
     }
     
-    fn symbol(env: Env) -> String {
+    fn unpause(env: Env, caller: Address) -> Result<(), TokenError> {
+        // 1. Verificar inicialización
         if !env.storage().instance().has(&DataKey::Initialized) {
-            return String::from_str(&env, "");
+            return Err(TokenError::NotInitialized);
         }
         
-        env.storage().instance()
-            .get(&DataKey::TokenSymbol)
-            .unwrap_or(String::from_str(&env, ""))
+        // 2. Solo cuentas con rol Pauser
+        roles::require_role(&env, Role::Pauser, &caller)?;
+        
+        // 3. Desactivar pausa y emitir evento
+        pause::set_paused(&env, false);
+        UnpausedEvent { caller, version: EVENT_SCHEMA_VERSION }.publish(&env);
+        
+        Ok(())
     }
     
-    fn decimals(env: Env) -> u32 {
+    fn set_authorized(env: Env, caller: Address, id: Address, authorize: bool) -> Result<(), TokenError> {
+        // 1. Verificar inicialización
         if !env.storage().instance().has(&DataKey::Initialized) {
-            return 0;
+            return Err(TokenError::NotInitialized);
         }
         
-        env.storage().instance()
-            .get(&DataKey::Decimals)
-            .unwrap_or(0)
+        // 2. Solo cuentas con rol Freezer
+        roles::require_role(&env, Role::Freezer, &caller)?;
+        
+        // 3. Guardar flag y emitir evento con el formato del SAC
+        authorization::write_authorized(&env, &id, authorize);
+        SetAuthorizedEvent { id, authorize }.publish(&env);
+        
+        Ok(())
+    }
+    
+    fn clawback(env: Env, caller: Address, from: Address, amount: i128) -> Result<(), TokenError> {
+        // 1. Verificar inicialización
+        if !env.storage().instance().has(&DataKey::Initialized) {
+            return Err(TokenError::NotInitialized);
+        }
+        
+        // 2. El token debe haberse creado con clawback habilitado
+        if !Self::clawback_enabled(env.clone()) {
+            return Err(TokenError::ClawbackDisabled);
+        }
+        
+        // 3. Solo el admin o una cuenta con rol Clawback
+        caller.require_auth();
+        if caller != admin::read_admin(&env)? && !roles::has_role(&env, Role::Clawback, &caller) {
+            return Err(TokenError::Unauthorized);
+        }
+        
+        // 4. Validaciones
+        if amount <= 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        
+        // 5. Reducir balance y total supply (sin firma del holder;
+        //    funciona aunque la cuenta esté congelada)
+        balance::spend_balance(&env, &from, amount)?;
+        balance::decrease_supply(&env, amount)?;
+        
+        // 6. Evento de clawback estándar + evento de BDB con quién lo
+        //    ejecutó
+        ClawbackEvent { from: from.clone(), amount }.publish(&env);
+        ClawbackCallerEvent { caller, from, amount, version: EVENT_SCHEMA_VERSION }.publish(&env);
+        
+        Ok(())
+    }
+    
+    fn set_max_supply(env: Env, max_supply: i128) -> Result<(), TokenError> {
+        // 1. Solo el admin
+        let admin = admin::require_admin(&env)?;
+        
+        // 2. El tope solo puede bajar...
+        if let Some(current_cap) = balance::read_max_supply(&env) {
+            if max_supply > current_cap {
+                return Err(TokenError::InvalidSupplyCap);
+            }
+        }
+        
+        // 3. ...y nunca por debajo de lo ya emitido
+        if max_supply <= 0 || max_supply < balance::read_total_supply(&env) {
+            return Err(TokenError::InvalidSupplyCap);
+        }
+        
+        // 4. Guardar y emitir evento
+        env.storage().instance().set(&DataKey::MaxSupply, &max_supply);
+        MaxSupplySetEvent { admin, max_supply, version: EVENT_SCHEMA_VERSION }.publish(&env);
+        
+        Ok(())
+    }
+    
+    fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), TokenError> {
+        // 1. Solo el admin
+        let admin = admin::require_admin(&env)?;
+        
+        // 2. Reemplazar el código (el storage no se toca)
+        env.deployer().update_current_contract_wasm(new_wasm_hash.clone());
+        
+        // 3. Emitir evento
+        UpgradedEvent { admin, new_wasm_hash, version: EVENT_SCHEMA_VERSION }.publish(&env);
+        
+        Ok(())
+    }
+    
+    fn migrate(env: Env) -> Result<u32, TokenError> {
+        // 1. Solo el admin
+        admin::require_admin(&env)?;
+        
+        // 2. Correr migraciones pendientes (cada una emite su evento)
+        upgrade::migrate(&env)
+    }
+    
+    fn batch_transfer(
+        env: Env,
+        from: Address,
+        recipients: Vec<(Address, i128)>
+    ) -> Result<(), TokenError> {
+        // 1. Verificar inicialización y que el contrato no esté pausado
+        if !env.storage().instance().has(&DataKey::Initialized) {
+            return Err(TokenError::NotInitialized);
+        }
+        pause::ensure_not_paused(&env)?;
+        
+        // 2. Una sola autorización para todo el lote
+        from.require_auth();
+        
+        if recipients.is_empty() {
+            return Err(TokenError::EmptyBatch);
+        }
+        if recipients.len() > MAX_BATCH {
+            return Err(TokenError::BatchTooLarge);
+        }
+        if !authorization::is_authorized(&env, &from) {
+            return Err(TokenError::AccountNotAuthorized);
+        }
+        
+        // 3. Validar cada entrada y calcular el total en tokens y en
+        //    shares (el índice que falla queda en los logs de diagnóstico).
+        //    Un monto que no llega a una share se rechaza: el lote
+        //    debita exactamente las shares que acredita
+        let mut total: i128 = 0;
+        let mut total_shares: i128 = 0;
+        for (index, (to, amount)) in recipients.iter().enumerate() {
+            if amount <= 0 || rebasing::to_shares(&env, amount) == 0 {
+                log!(&env, "batch_transfer: monto inválido en el índice", index as u32);
+                return Err(TokenError::InvalidAmount);
+            }
+            if to == from {
+                log!(&env, "batch_transfer: destinatario inválido en el índice", index as u32);
+                return Err(TokenError::InvalidRecipient);
+            }
+            if !authorization::is_authorized(&env, &to) {
+                log!(&env, "batch_transfer: cuenta congelada en el índice", index as u32);
+                return Err(TokenError::AccountNotAuthorized);
+            }
+            total = total.checked_add(amount)
+                .ok_or(TokenError::OverflowError)?;
+            total_shares = total_shares.checked_add(rebasing::to_shares(&env, amount))
+                .ok_or(TokenError::OverflowError)?;
+        }
+        
+        // 4. Debitar el total de una vez (falla si no alcanza el balance)
+        balance::spend_shares(&env, &from, total_shares)?;
+        
+        // 5. Acreditar a cada destinatario (descontando el fee si corresponde)
+        for (to, amount) in recipients.iter() {
+            credit_transfer(&env, &from, &to, None, amount, rebasing::to_shares(&env, amount))?;
+        }
+        
+        // 6. Evento resumen con (count, total)
+        BatchTransferEvent {
+            from,
+            count: recipients.len(),
+            total,
+            version: EVENT_SCHEMA_VERSION,
+        }.publish(&env);
+        
+        Ok(())
+    }
+    
+    fn batch_mint(
+        env: Env,
+        minter: Address,
+        recipients: Vec<(Address, i128)>
+    ) -> Result<(), TokenError> {
+        // 1. Verificar inicialización y que el contrato no esté pausado
+        if !env.storage().instance().has(&DataKey::Initialized) {
+            return Err(TokenError::NotInitialized);
+        }
+        pause::ensure_not_paused(&env)?;
+        
+        // 2. Una sola autorización del minter para todo el lote
+        roles::require_role(&env, Role::Minter, &minter)?;
+        
+        if recipients.is_empty() {
+            return Err(TokenError::EmptyBatch);
+        }
+        if recipients.len() > MAX_BATCH {
+            return Err(TokenError::BatchTooLarge);
+        }
+        
+        // 3. Validar cada entrada y calcular el total en tokens y en
+        //    shares (el supply sube exactamente lo que se acredita)
+        let mut total: i128 = 0;
+        let mut total_shares: i128 = 0;
+        for (index, (to, amount)) in recipients.iter().enumerate() {
+            let shares = match validate_mint_entry(&env, &to, amount) {
+                Ok(shares) => shares,
+                Err(error) => {
+                    log!(&env, "batch_mint: entrada inválida en el índice", index as u32);
+                    return Err(error);
+                }
+            };
+            total = total.checked_add(amount)
+                .ok_or(TokenError::OverflowError)?;
+            total_shares = total_shares.checked_add(shares)
+                .ok_or(TokenError::OverflowError)?;
+        }
+        
+        // 4. Cuota del minter (si tiene una) y supply total una sola vez:
+        //    el tope de max_supply se valida contra el total del lote
+        let remaining_quota = quota::consume(&env, &minter, total)?;
+        balance::increase_total_shares(&env, total_shares)?;
+        
+        // 5. Acreditar a cada destinatario con el evento estándar de mint
+        for (to, amount) in recipients.iter() {
+            balance::receive_shares(&env, &to, rebasing::to_shares(&env, amount))?;
+            MintEvent { to, amount }.publish(&env);
+        }
+        
+        // 6. Evento resumen con (count, total) y, si hay cuota, cuánto
+        //    le queda al minter en el período
+        BatchMintEvent {
+            minter,
+            count: recipients.len(),
+            total,
+            remaining_quota,
+            version: EVENT_SCHEMA_VERSION,
+        }.publish(&env);
+        
+        Ok(())
+    }
+    
+    fn first_invalid_mint_entry(env: Env, recipients: Vec<(Address, i128)>) -> Option<u32> {
+        recipients.iter()
+            .position(|(to, amount)| validate_mint_entry(&env, &to, amount).is_err())
+            .map(|index| index as u32)
+    }
+    
+    fn balance_at(env: Env, account: Address, ledger: u32) -> Result<i128, TokenError> {
+        checkpoints::balance_at(&env, &account, ledger)
+    }
+    
+    fn total_supply_at(env: Env, ledger: u32) -> Result<i128, TokenError> {
+        checkpoints::total_supply_at(&env, ledger)
+    }
+    
+    fn set_checkpoint_horizon(env: Env, ledger: u32) -> Result<(), TokenError> {
+        // 1. Solo el admin
+        let admin = admin::require_admin(&env)?;
+        
+        // 2. Subir el horizonte (valida que no baje ni pase el ledger actual)
+        checkpoints::write_horizon(&env, ledger)?;
+        
+        // 3. Podar el historial del supply (acotado por llamada)
+        checkpoints::prune_supply(&env);
+        
+        // 4. Emitir evento
+        CheckpointHorizonEvent { admin, ledger, version: EVENT_SCHEMA_VERSION }.publish(&env);
+        
+        Ok(())
+    }
+    
+    fn prune_checkpoints(env: Env, account: Address) -> Result<u32, TokenError> {
+        if !env.storage().instance().has(&DataKey::Initialized) {
+            return Err(TokenError::NotInitialized);
+        }
+        Ok(checkpoints::prune_balance(&env, &account) + votes::prune_votes(&env, &account))
+    }
+    
+    fn delegate(env: Env, delegator: Address, delegatee: Address) -> Result<(), TokenError> {
+        // 1. Verificar inicialización
+        if !env.storage().instance().has(&DataKey::Initialized) {
+            return Err(TokenError::NotInitialized);
+        }
+        
+        // 2. Solo la propia cuenta delega sus votos
+        delegator.require_auth();
+        
+        // 3. Cambiar delegado y mover el balance actual
+        //    (cada delegado afectado emite su votes_chg)
+        let previous = votes::delegate(&env, &delegator, &delegatee);
+        
+        // 4. Evento con el delegado anterior y el nuevo
+        DelegateChangedEvent {
+            delegator,
+            previous,
+            delegatee,
+            version: EVENT_SCHEMA_VERSION,
+        }.publish(&env);
+        
+        Ok(())
+    }
+    
+    fn permit(
+        env: Env,
+        owner: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+        nonce: u64,
+        deadline_ledger: u32,
+        public_key: BytesN<32>,
+        signature: BytesN<64>
+    ) -> Result<(), TokenError> {
+        // 1. Verificar inicialización
+        if !env.storage().instance().has(&DataKey::Initialized) {
+            return Err(TokenError::NotInitialized);
+        }
+        
+        // 2. La firma solo vale hasta deadline_ledger
+        if env.ledger().sequence() > deadline_ledger {
+            return Err(TokenError::PermitExpired);
+        }
+        
+        // 3. La clave que firmó debe ser la de la cuenta owner
+        if !permit::owner_matches_key(&env, &owner, &public_key) {
+            return Err(TokenError::Unauthorized);
+        }
+        
+        // 4. Validación: amount >= 0 (0 revoca, igual que approve)
+        if amount < 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        
+        // 5. Verificar la firma sobre el payload con dominio
+        //    (ed25519_verify aborta si la firma no es válida)
+        let payload = permit::payload(
+            &env,
+            &owner,
+            &spender,
+            amount,
+            expiration_ledger,
+            nonce,
+            deadline_ledger
+        );
+        env.crypto().ed25519_verify(&public_key, &payload, &signature);
+        
+        // 6. Consumir el nonce: la misma firma no sirve dos veces
+        permit::consume_nonce(&env, &owner, nonce)?;
+        
+        // 7. Escribir el allowance (valida expiration_ledger y ajusta el TTL)
+        allowance::write_allowance(&env, &owner, &spender, amount, expiration_ledger)?;
+        
+        // 8. Mismo evento que approve
+        ApproveEvent { from: owner, spender, amount, expiration_ledger }.publish(&env);
+        
+        Ok(())
+    }
+    
+    fn set_transfer_fee(env: Env, fee_bps: u32, treasury: Address) -> Result<(), TokenError> {
+        // 1. Solo el admin
+        let admin = admin::require_admin(&env)?;
+        
+        // 2. El techo es fijo: ningún admin puede superarlo
+        if fee_bps > MAX_FEE_BPS {
+            return Err(TokenError::FeeTooHigh);
+        }
+        
+        // 3. Guardar y emitir evento
+        fee::write_config(&env, &FeeConfig { fee_bps, treasury: treasury.clone() });
+        FeeSetEvent { admin, fee_bps, treasury, version: EVENT_SCHEMA_VERSION }.publish(&env);
+        
+        Ok(())
+    }
+    
+    fn set_fee_exempt(env: Env, account: Address, exempt: bool) -> Result<(), TokenError> {
+        // 1. Solo el admin
+        let admin = admin::require_admin(&env)?;
+        
+        // 2. Guardar flag y emitir evento
+        fee::write_exempt(&env, &account, exempt);
+        FeeExemptEvent { admin, account, exempt, version: EVENT_SCHEMA_VERSION }.publish(&env);
+        
+        Ok(())
+    }
+    
+    fn transfer_and_call(
+        env: Env,
+        from: Address,
+        to_contract: Address,
+        amount: i128,
+        data: Bytes
+    ) -> Result<(), TokenError> {
+        // 1. Verificar inicialización y que el contrato no esté pausado
+        if !env.storage().instance().has(&DataKey::Initialized) {
+            return Err(TokenError::NotInitialized);
+        }
+        pause::ensure_not_paused(&env)?;
+        
+        // 2. Verificar autorización del sender
+        from.require_auth();
+        
+        // 3. Validaciones (mismas que transfer)
+        if amount <= 0 {
+            return Err(TokenError::InvalidAmount);
+        }
+        if from == to_contract {
+            return Err(TokenError::InvalidRecipient);
+        }
+        if !authorization::is_authorized(&env, &from) || !authorization::is_authorized(&env, &to_contract) {
+            return Err(TokenError::AccountNotAuthorized);
+        }
+        
+        // 4. Mover los tokens antes del hook: el receptor ya los tiene
+        //    cuando se entera
+        let shares = balance::spend_balance(&env, &from, amount)?;
+        let received = credit_transfer(&env, &from, &to_contract, None, amount, shares)?;
+        
+        // 5. Hook del receptor. Devolver Err revierte también el paso 4
+        let result = env.try_invoke_contract::<(), InvokeError>(
+            &to_contract,
+            &Symbol::new(&env, "on_token_received"),
+            vec![
+                &env,
+                env.current_contract_address().into_val(&env),
+                from.into_val(&env),
+                received.into_val(&env),
+                data.into_val(&env),
+            ]
+        );
+        if !matches!(result, Ok(Ok(()))) {
+            return Err(TokenError::ReceiverRejected);
+        }
+        
+        Ok(())
+    }
+    
+    fn rebase(env: Env, caller: Address, new_index: i128) -> Result<(), TokenError> {
+        // 1. Verificar inicialización
+        if !env.storage().instance().has(&DataKey::Initialized) {
+            return Err(TokenError::NotInitialized);
+        }
+        
+        // 2. Solo tokens creados en modo rebasing
+        if !rebasing::is_enabled(&env) {
+            return Err(TokenError::NotRebasing);
+        }
+        
+        // 3. Solo el admin o una cuenta con rol Oracle
+        caller.require_auth();
+        if caller != admin::read_admin(&env)? && !roles::has_role(&env, Role::Oracle, &caller) {
+            return Err(TokenError::Unauthorized);
+        }
+        
+        // 4. Validar el índice (puede bajar: p. ej. una pérdida del vault)
+        if new_index <= 0 {
+            return Err(TokenError::InvalidRebaseIndex);
+        }
+        
+        // 5. Guardar (con checkpoint) y emitir evento con (old, new)
+        let old_index = rebasing::read_index(&env);
+        rebasing::write_index(&env, new_index);
+        RebaseEvent { caller, old_index, new_index, version: EVENT_SCHEMA_VERSION }.publish(&env);
+        
+        Ok(())
+    }
+    
+    fn set_metadata(
+        env: Env,
+        caller: Address,
+        name: String,
+        symbol: String,
+        description: Option<String>,
+        image_uri: Option<String>,
+        home_domain: Option<String>,
+        website: Option<String>
+    ) -> Result<(), TokenError> {
+        // 1. Verificar inicialización
+        if !env.storage().instance().has(&DataKey::Initialized) {
+            return Err(TokenError::NotInitialized);
+        }
+        
+        // 2. Solo el admin o una cuenta con rol MetadataManager
+        caller.require_auth();
+        if caller != admin::read_admin(&env)?
+            && !roles::has_role(&env, Role::MetadataManager, &caller)
+        {
+            return Err(TokenError::Unauthorized);
+        }
+        
+        // 3. Validar (decimals se conserva)
+        let new_metadata = TokenMetadata {
+            name,
+            symbol,
+            decimals: metadata::read(&env).decimals,
+            description,
+            image_uri,
+            home_domain,
+            website,
+        };
+        metadata::validate(&new_metadata)?;
+        
+        // 4. Guardar y emitir evento con la metadata completa
+        metadata::write(&env, &new_metadata);
+        MetadataUpdatedEvent {
+            caller,
+            metadata: new_metadata,
+            version: EVENT_SCHEMA_VERSION,
+        }.publish(&env);
+        
+        Ok(())
+    }
+    
+    fn get_votes(env: Env, account: Address) -> i128 {
+        votes::get_votes(&env, &account)
+    }
+    
+    fn get_past_votes(env: Env, account: Address, ledger: u32) -> Result<i128, TokenError> {
+        votes::get_past_votes(&env, &account, ledger)
+    }
+    
+    fn extend_balance_ttl(env: Env, account: Address) -> Result<(), TokenError> {
+        balance::extend_balance_ttl(&env, &account)
+    }
+    
+    fn extend_allowance_ttl(env: Env, owner: Address, spender: Address) -> Result<(), TokenError> {
+        allowance::extend_allowance_ttl(&env, &owner, &spender)
+    }
+    
+    fn extend_instance_ttl(env: Env) -> Result<(), TokenError> {
+        if !env.storage().instance().has(&DataKey::Initialized) {
+            return Err(TokenError::NotInitialized);
+        }
+        storage::extend_instance(&env);
+        Ok(())
+    }
+    
+    fn set_ttl_config(env: Env, config: TtlConfig) -> Result<(), TokenError> {
+        // 1. Solo el admin
+        let admin = admin::require_admin(&env)?;
+        
+        // 2. Validar: threshold <= extend_to <= máximo permitido por la red
+        let max_ttl = env.storage().max_ttl();
+        if config.instance_threshold > config.instance_extend_to
+            || config.persistent_threshold > config.persistent_extend_to
+            || config.instance_extend_to > max_ttl
+            || config.persistent_extend_to > max_ttl
+        {
+            return Err(TokenError::InvalidTtlConfig);
+        }
+        
+        // 3. Guardar y emitir evento
+        env.storage().instance().set(&DataKey::TtlConfig, &config);
+        TtlConfigEvent { admin, config, version: EVENT_SCHEMA_VERSION }.publish(&env);
+        
+        Ok(())
     }
     
     fn total_supply(env: Env) -> i128 {
-        env.storage().instance()
-            .get(&DataKey::TotalSupply)
-            .unwrap_or(0)
+        balance::read_total_supply(&env)
+    }
+    
+    fn metadata(env: Env) -> Result<TokenMetadata, TokenError> {
+        if !env.storage().instance().has(&DataKey::Initialized) {
+            return Err(TokenError::NotInitialized);
+        }
+        Ok(metadata::read(&env))
     }
     
     fn admin(env: Env) -> Address {
@@ -541,4 +1449,276 @@ impl TokenTrait for TokenBDB {
             .get(&DataKey::Admin)
             .expect("Admin not initialized")
     }
-}
\ No newline at end of file
+    
+    fn pending_admin(env: Env) -> Option<PendingAdmin> {
+        admin::read_pending_admin(&env)
+    }
+    
+    fn has_role(env: Env, role: Role, account: Address) -> bool {
+        roles::has_role(&env, role, &account)
+    }
+    
+    fn is_paused(env: Env) -> bool {
+        pause::is_paused(&env)
+    }
+    
+    fn authorized(env: Env, id: Address) -> bool {
+        authorization::is_authorized(&env, &id)
+    }
+    
+    fn clawback_enabled(env: Env) -> bool {
+        env.storage().instance()
+            .get(&DataKey::ClawbackEnabled)
+            .unwrap_or(false)
+    }
+    
+    fn max_supply(env: Env) -> Option<i128> {
+        balance::read_max_supply(&env)
+    }
+    
+    fn minter_quota(env: Env, minter: Address) -> Option<MinterQuota> {
+        quota::read_quota(&env, &minter)
+    }
+    
+    fn schema_version(env: Env) -> u32 {
+        upgrade::read_schema_version(&env)
+    }
+    
+    fn ttl_config(env: Env) -> TtlConfig {
+        storage::read_ttl_config(&env)
+    }
+    
+    fn checkpoint_horizon(env: Env) -> u32 {
+        checkpoints::read_horizon(&env)
+    }
+    
+    fn delegates(env: Env, account: Address) -> Option<Address> {
+        votes::read_delegate(&env, &account)
+    }
+    
+    fn nonce(env: Env, owner: Address) -> u64 {
+        permit::read_nonce(&env, &owner)
+    }
+    
+    fn transfer_fee(env: Env) -> Option<FeeConfig> {
+        fee::read_config(&env)
+    }
+    
+    fn is_fee_exempt(env: Env, account: Address) -> bool {
+        fee::is_exempt(&env, &account)
+    }
+    
+    fn is_rebasing(env: Env) -> bool {
+        rebasing::is_enabled(&env)
+    }
+    
+    fn rebase_index(env: Env) -> i128 {
+        rebasing::read_index(&env)
+    }
+    
+    fn shares_of(env: Env, account: Address) -> i128 {
+        balance::read_shares(&env, &account)
+    }
+    
+    fn total_shares(env: Env) -> i128 {
+        balance::read_total_shares(&env)
+    }
+    
+    fn holder_count(env: Env) -> u32 {
+        holders::read_count(&env)
+    }
+    
+    fn holders(env: Env, start: u32, limit: u32) -> Result<HolderPage, TokenError> {
+        holders::page(&env, start, limit)
+    }
+}
+
+/// Implementación de la interfaz estándar SEP-41
+/// 
+/// Las firmas y los eventos siguen exactamente `token::TokenInterface`,
+/// por lo que DEXs, vaults y wallets pueden manejar BDB con
+/// `token::TokenClient` sin conocer este contrato.
+#[contractimpl]
+impl token::TokenInterface for TokenBDB {
+    fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        allowance::read_allowance(&env, &from, &spender)
+    }
+    
+    fn approve(
+        env: Env, 
+        from: Address, 
+        spender: Address, 
+        amount: i128,
+        expiration_ledger: u32
+    ) {
+        // 1. Verificar inicialización
+        require_initialized(&env);
+        
+        // 2. Verificar autorización del owner
+        from.require_auth();
+        
+        // 3. Validación: amount debe ser >= 0 (permitir 0 para revocar)
+        if amount < 0 {
+            panic_with_error!(&env, TokenError::InvalidAmount);
+        }
+        
+        // 4. Actualizar allowance (valida expiration_ledger y ajusta el TTL)
+        unwrap_or_panic(
+            &env,
+            allowance::write_allowance(&env, &from, &spender, amount, expiration_ledger)
+        );
+        
+        // 5. Evento estándar de approve
+        ApproveEvent { from, spender, amount, expiration_ledger }.publish(&env);
+    }
+    
+    fn balance(env: Env, id: Address) -> i128 {
+        balance::read_balance(&env, &id)
+    }
+    
+    fn transfer(env: Env, from: Address, to: MuxedAddress, amount: i128) {
+        // 1. Verificar inicialización y que el contrato no esté pausado
+        require_initialized(&env);
+        require_not_paused(&env);
+        
+        // 2. Verificar autorización del sender
+        from.require_auth();
+        
+        // 3. Validaciones
+        require_positive(&env, amount);
+        
+        // 4. No permitir transferencia a sí mismo (gas-efficient)
+        let to_muxed_id = to.id();
+        let to = to.address();
+        if from == to {
+            panic_with_error!(&env, TokenError::InvalidRecipient);
+        }
+        
+        // Ninguna de las dos cuentas puede estar congelada
+        require_authorized(&env, &from);
+        require_authorized(&env, &to);
+        
+        // 5. Debitar el monto bruto y acreditar el neto (y el fee, si hay).
+        //    Evento estándar: topics ["transfer", from, to]
+        //    data = amount, o { to_muxed_id, amount } si el destino es muxed
+        let shares = unwrap_or_panic(&env, balance::spend_balance(&env, &from, amount));
+        unwrap_or_panic(&env, credit_transfer(&env, &from, &to, to_muxed_id, amount, shares));
+    }
+    
+    fn transfer_from(
+        env: Env, 
+        spender: Address, 
+        from: Address, 
+        to: Address, 
+        amount: i128
+    ) {
+        // 1. Verificar inicialización y que el contrato no esté pausado
+        require_initialized(&env);
+        require_not_paused(&env);
+        
+        // 2. Verificar autorización del spender
+        spender.require_auth();
+        
+        // 3. Validaciones
+        require_positive(&env, amount);
+        
+        // 4. No permitir transferencia a sí mismo
+        if from == to {
+            panic_with_error!(&env, TokenError::InvalidRecipient);
+        }
+        
+        // Ninguna de las dos cuentas puede estar congelada
+        require_authorized(&env, &from);
+        require_authorized(&env, &to);
+        
+        // 5. Consumir allowance y mover balances (todo o nada:
+        //    cualquier error revierte la invocación completa).
+        //    El allowance se consume por el monto bruto
+        unwrap_or_panic(&env, allowance::spend_allowance(&env, &from, &spender, amount));
+        let shares = unwrap_or_panic(&env, balance::spend_balance(&env, &from, amount));
+        
+        // 6. Acreditar el neto (y el fee, si hay).
+        //    Evento estándar: topics ["transfer", from, to], data = amount
+        unwrap_or_panic(&env, credit_transfer(&env, &from, &to, None, amount, shares));
+    }
+    
+    fn burn(env: Env, from: Address, amount: i128) {
+        // 1. Verificar inicialización y que el contrato no esté pausado
+        require_initialized(&env);
+        require_not_paused(&env);
+        
+        // 2. Requiere autorización del dueño de los tokens
+        from.require_auth();
+        
+        // 3. Validaciones (una cuenta congelada tampoco quema)
+        require_positive(&env, amount);
+        require_authorized(&env, &from);
+        
+        // 4. Reducir balance y total supply
+        unwrap_or_panic(&env, balance::spend_balance(&env, &from, amount));
+        unwrap_or_panic(&env, balance::decrease_supply(&env, amount));
+        
+        // 5. Evento estándar de burn
+        BurnEvent { from, amount }.publish(&env);
+    }
+    
+    /// Quema tokens de `from` en nombre de un spender aprobado
+    /// 
+    /// Pensado para contratos de redención: el holder aprueba al
+    /// contrato y este quema sin necesitar la firma del holder.
+    /// Consume el allowance igual que transfer_from.
+    fn burn_from(env: Env, spender: Address, from: Address, amount: i128) {
+        // 1. Verificar inicialización y que el contrato no esté pausado
+        require_initialized(&env);
+        require_not_paused(&env);
+        
+        // 2. Verificar autorización del spender (no del holder)
+        spender.require_auth();
+        
+        // 3. Validaciones (una cuenta congelada tampoco quema)
+        require_positive(&env, amount);
+        require_authorized(&env, &from);
+        
+        // 4. Consumir allowance, reducir balance y total supply
+        //    (balances y allowances en 0 se eliminan del storage)
+        unwrap_or_panic(&env, allowance::spend_allowance(&env, &from, &spender, amount));
+        unwrap_or_panic(&env, balance::spend_balance(&env, &from, amount));
+        unwrap_or_panic(&env, balance::decrease_supply(&env, amount));
+        
+        // 5. Evento de burn estándar (SEP-41) + evento de BDB con el
+        //    spender que ejecutó la quema
+        BurnEvent { from: from.clone(), amount }.publish(&env);
+        BurnFromEvent { from, spender, amount, version: EVENT_SCHEMA_VERSION }.publish(&env);
+    }
+    
+    fn decimals(env: Env) -> u32 {
+        if !env.storage().instance().has(&DataKey::Initialized) {
+            return 0;
+        }
+        
+        env.storage().instance()
+            .get(&DataKey::Decimals)
+            .unwrap_or(0)
+    }
+    
+    fn name(env: Env) -> String {
+        // Verificar inicialización antes de devolver metadata
+        if !env.storage().instance().has(&DataKey::Initialized) {
+            return String::from_str(&env, "");
+        }
+        
+        env.storage().instance()
+            .get(&DataKey::TokenName)
+            .unwrap_or(String::from_str(&env, ""))
+    }
+    
+    fn symbol(env: Env) -> String {
+        if !env.storage().instance().has(&DataKey::Initialized) {
+            return String::from_str(&env, "");
+        }
+        
+        env.storage().instance()
+            .get(&DataKey::TokenSymbol)
+            .unwrap_or(String::from_str(&env, ""))
+    }
+}
@@ -0,0 +1,77 @@
+// src/metadata.rs
+use soroban_sdk::{Env, String};
+
+use crate::errors::TokenError;
+use crate::storage::{DataKey, TokenMetadata};
+use crate::{
+    MAX_DESCRIPTION_LENGTH, MAX_HOME_DOMAIN_LENGTH, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH,
+    MAX_URI_LENGTH
+};
+
+/// Valida name y symbol (no vacíos y dentro del máximo)
+///
+/// La usan initialize() y set_metadata(), así las reglas son las mismas
+pub fn validate_name_symbol(name: &String, symbol: &String) -> Result<(), TokenError> {
+    if name.is_empty() || name.len() > MAX_NAME_LENGTH {
+        return Err(TokenError::InvalidMetadata);
+    }
+    if symbol.is_empty() || symbol.len() > MAX_SYMBOL_LENGTH {
+        return Err(TokenError::InvalidMetadata);
+    }
+    Ok(())
+}
+
+/// Valida un campo opcional: None es válido (sin valor), pero un
+/// string vacío no (para borrar el campo se pasa None)
+fn validate_optional(value: &Option<String>, max_length: u32) -> Result<(), TokenError> {
+    match value {
+        Some(value) if value.is_empty() || value.len() > max_length => {
+            Err(TokenError::InvalidMetadata)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Valida todos los campos modificables de `metadata`
+pub fn validate(metadata: &TokenMetadata) -> Result<(), TokenError> {
+    validate_name_symbol(&metadata.name, &metadata.symbol)?;
+    validate_optional(&metadata.description, MAX_DESCRIPTION_LENGTH)?;
+    validate_optional(&metadata.image_uri, MAX_URI_LENGTH)?;
+    validate_optional(&metadata.home_domain, MAX_HOME_DOMAIN_LENGTH)?;
+    validate_optional(&metadata.website, MAX_URI_LENGTH)
+}
+
+/// Lee la metadata completa (campos opcionales sin key = None)
+pub fn read(env: &Env) -> TokenMetadata {
+    let instance = env.storage().instance();
+    TokenMetadata {
+        name: instance.get(&DataKey::TokenName).unwrap_or(String::from_str(env, "")),
+        symbol: instance.get(&DataKey::TokenSymbol).unwrap_or(String::from_str(env, "")),
+        decimals: instance.get(&DataKey::Decimals).unwrap_or(0),
+        description: instance.get(&DataKey::TokenDescription),
+        image_uri: instance.get(&DataKey::TokenImageUri),
+        home_domain: instance.get(&DataKey::HomeDomain),
+        website: instance.get(&DataKey::TokenWebsite),
+    }
+}
+
+/// Guarda un campo opcional; None elimina la key
+fn write_optional(env: &Env, key: &DataKey, value: &Option<String>) {
+    match value {
+        Some(value) => env.storage().instance().set(key, value),
+        None => env.storage().instance().remove(key),
+    }
+}
+
+/// Guarda los campos modificables de `metadata`
+///
+/// `decimals` se ignora: se fija en initialize() y nunca cambia
+/// (cambiarlo alteraría el valor de todos los balances)
+pub fn write(env: &Env, metadata: &TokenMetadata) {
+    env.storage().instance().set(&DataKey::TokenName, &metadata.name);
+    env.storage().instance().set(&DataKey::TokenSymbol, &metadata.symbol);
+    write_optional(env, &DataKey::TokenDescription, &metadata.description);
+    write_optional(env, &DataKey::TokenImageUri, &metadata.image_uri);
+    write_optional(env, &DataKey::HomeDomain, &metadata.home_domain);
+    write_optional(env, &DataKey::TokenWebsite, &metadata.website);
+}
@@ -0,0 +1,31 @@
+// src/pause.rs
+use soroban_sdk::Env;
+
+use crate::errors::TokenError;
+use crate::storage::DataKey;
+
+/// Consulta si el contrato está pausado
+pub fn is_paused(env: &Env) -> bool {
+    env.storage().instance()
+        .get(&DataKey::Paused)
+        .unwrap_or(false)
+}
+
+/// Activa o desactiva la pausa
+///
+/// Al despausar eliminamos la key en vez de guardar false
+pub fn set_paused(env: &Env, paused: bool) {
+    if paused {
+        env.storage().instance().set(&DataKey::Paused, &true);
+    } else {
+        env.storage().instance().remove(&DataKey::Paused);
+    }
+}
+
+/// Falla con ContractPaused si el contrato está pausado
+pub fn ensure_not_paused(env: &Env) -> Result<(), TokenError> {
+    if is_paused(env) {
+        return Err(TokenError::ContractPaused);
+    }
+    Ok(())
+}
@@ -0,0 +1,62 @@
+// src/permit.rs
+use soroban_sdk::{xdr::ToXdr, Address, Bytes, BytesN, Env};
+
+use crate::errors::TokenError;
+use crate::storage::{self, DataKey, PermitPayload};
+
+/// Prefijo XDR de un ScVal con la dirección de una cuenta ed25519
+///
+/// SCV_ADDRESS (18), SC_ADDRESS_TYPE_ACCOUNT (0), PUBLIC_KEY_TYPE_ED25519 (0);
+/// después vienen los 32 bytes de la clave pública
+const ACCOUNT_XDR_PREFIX: [u8; 12] = [0, 0, 0, 18, 0, 0, 0, 0, 0, 0, 0, 0];
+
+/// Lee el próximo nonce de `owner` (0 si nunca usó permit)
+pub fn read_nonce(env: &Env, owner: &Address) -> u64 {
+    env.storage().persistent()
+        .get(&DataKey::Nonce(owner.clone()))
+        .unwrap_or(0)
+}
+
+/// Verifica que `nonce` sea el próximo de `owner` y lo consume
+pub fn consume_nonce(env: &Env, owner: &Address, nonce: u64) -> Result<(), TokenError> {
+    if nonce != read_nonce(env, owner) {
+        return Err(TokenError::InvalidNonce);
+    }
+    let key = DataKey::Nonce(owner.clone());
+    env.storage().persistent().set(&key, &(nonce + 1));
+    storage::extend_persistent(env, &key);
+    Ok(())
+}
+
+/// Devuelve true si `owner` es la cuenta G... de `public_key`
+///
+/// Sin este chequeo cualquiera podría firmar con su propia clave
+/// un permit sobre los tokens de otro
+pub fn owner_matches_key(env: &Env, owner: &Address, public_key: &BytesN<32>) -> bool {
+    let mut expected = Bytes::from_array(env, &ACCOUNT_XDR_PREFIX);
+    expected.append(&public_key.clone().into());
+    owner.clone().to_xdr(env) == expected
+}
+
+/// Mensaje a firmar: XDR del PermitPayload con el dominio de este contrato
+pub fn payload(
+    env: &Env,
+    owner: &Address,
+    spender: &Address,
+    amount: i128,
+    expiration_ledger: u32,
+    nonce: u64,
+    deadline_ledger: u32
+) -> Bytes {
+    PermitPayload {
+        contract: env.current_contract_address(),
+        network_id: env.ledger().network_id(),
+        owner: owner.clone(),
+        spender: spender.clone(),
+        amount,
+        expiration_ledger,
+        nonce,
+        deadline_ledger,
+    }
+    .to_xdr(env)
+}
@@ -0,0 +1,53 @@
+// src/quota.rs
+use soroban_sdk::{Address, Env};
+
+use crate::errors::TokenError;
+use crate::storage::{self, DataKey, MinterQuota};
+
+/// Lee la cuota de un minter con la ventana ya actualizada
+///
+/// Si el período terminó, devuelve la cuota con `used` en 0 y la
+/// ventana arrancando en el ledger actual (sin escribir storage).
+/// None = el minter no tiene cuota (sin límite)
+pub fn read_quota(env: &Env, minter: &Address) -> Option<MinterQuota> {
+    let mut quota: MinterQuota = env.storage().persistent()
+        .get(&DataKey::MinterQuota(minter.clone()))?;
+    let current = env.ledger().sequence();
+    if current >= quota.window_start.saturating_add(quota.period_ledgers) {
+        quota.window_start = current;
+        quota.used = 0;
+    }
+    Some(quota)
+}
+
+/// Guarda la cuota de un minter
+pub fn write_quota(env: &Env, minter: &Address, quota: &MinterQuota) {
+    let key = DataKey::MinterQuota(minter.clone());
+    env.storage().persistent().set(&key, quota);
+    storage::extend_persistent(env, &key);
+}
+
+/// Elimina la cuota de un minter (vuelve a no tener límite)
+pub fn remove_quota(env: &Env, minter: &Address) {
+    env.storage().persistent().remove(&DataKey::MinterQuota(minter.clone()));
+}
+
+/// Consume `amount` de la cuota del minter
+///
+/// Devuelve lo que queda disponible en el período, o None si el
+/// minter no tiene cuota configurada
+pub fn consume(env: &Env, minter: &Address, amount: i128) -> Result<Option<i128>, TokenError> {
+    let Some(mut quota) = read_quota(env, minter) else {
+        return Ok(None);
+    };
+
+    let new_used = quota.used.checked_add(amount)
+        .ok_or(TokenError::OverflowError)?;
+    if new_used > quota.limit {
+        return Err(TokenError::MinterQuotaExceeded);
+    }
+
+    quota.used = new_used;
+    write_quota(env, minter, &quota);
+    Ok(Some(quota.limit - quota.used))
+}
@@ -0,0 +1,66 @@
+// src/rebasing.rs
+use soroban_sdk::Env;
+
+use crate::checkpoints;
+use crate::storage::{CheckpointSeries, DataKey};
+
+/// Escala del índice de rebase: INDEX_SCALE = 1.0
+///
+/// Con 9 decimales de precisión el índice puede expresar
+/// rendimientos de hasta 0.0000001% por rebase
+pub const INDEX_SCALE: i128 = 1_000_000_000;
+
+/// Consulta si el token se creó en modo rebasing
+///
+/// En ese modo DataKey::Balance y DataKey::TotalSupply guardan shares
+pub fn is_enabled(env: &Env) -> bool {
+    env.storage().instance()
+        .get(&DataKey::Rebasing)
+        .unwrap_or(false)
+}
+
+/// Lee el índice actual (INDEX_SCALE si nunca hubo rebase)
+pub fn read_index(env: &Env) -> i128 {
+    env.storage().instance()
+        .get(&DataKey::RebaseIndex)
+        .unwrap_or(INDEX_SCALE)
+}
+
+/// Guarda el nuevo índice y su checkpoint (para balance_at)
+pub fn write_index(env: &Env, index: i128) {
+    checkpoints::record(env, &CheckpointSeries::RebaseIndex, read_index(env), index);
+    env.storage().instance().set(&DataKey::RebaseIndex, &index);
+}
+
+/// Convierte tokens a shares con el índice actual
+///
+/// Redondea hacia abajo. Cada operación convierte una sola vez y
+/// mueve esas shares de punta a punta: transfer reparte entre destino
+/// y treasury las mismas shares que debita, y los lotes acreditan (o
+/// suman al total) exactamente las shares de cada entrada. Así la suma
+/// de las cuentas siempre es igual al total de shares.
+/// En modo estándar devuelve `amount` sin cambios
+pub fn to_shares(env: &Env, amount: i128) -> i128 {
+    if !is_enabled(env) {
+        return amount;
+    }
+    let index = read_index(env);
+    // floor(amount * INDEX_SCALE / index) sin multiplicar amount completo
+    (amount / index) * INDEX_SCALE + (amount % index) * INDEX_SCALE / index
+}
+
+/// Convierte shares a tokens con el índice actual (redondeo hacia abajo)
+///
+/// En modo estándar devuelve `shares` sin cambios
+pub fn to_amount(env: &Env, shares: i128) -> i128 {
+    if !is_enabled(env) {
+        return shares;
+    }
+    to_amount_at(shares, read_index(env))
+}
+
+/// Convierte shares a tokens con un índice dado (redondeo hacia abajo)
+pub fn to_amount_at(shares: i128, index: i128) -> i128 {
+    // floor(shares * index / INDEX_SCALE) sin multiplicar shares completo
+    (shares / INDEX_SCALE) * index + (shares % INDEX_SCALE) * index / INDEX_SCALE
+}
@@ -0,0 +1,69 @@
+// src/roles.rs
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::admin;
+use crate::errors::TokenError;
+use crate::storage::{self, DataKey, Role};
+
+/// Todos los roles, para asignarlos al admin en initialize()
+pub const ALL_ROLES: [Role; 5] = [
+    Role::Minter,
+    Role::Pauser,
+    Role::Freezer,
+    Role::MetadataManager,
+    Role::RoleAdmin,
+];
+
+/// Roles que no se dan en initialize() pero el admin puede tener
+/// (Clawback y Oracle se otorgan aparte)
+const OPTIONAL_ROLES: [Role; 2] = [Role::Clawback, Role::Oracle];
+
+/// Consulta si `account` tiene `role`
+///
+/// El admin (DataKey::Admin) siempre cuenta como RoleAdmin, así
+/// puede recuperar el control de los roles aunque se revoquen todos
+pub fn has_role(env: &Env, role: Role, account: &Address) -> bool {
+    if env.storage().persistent().has(&DataKey::Role(role, account.clone())) {
+        return true;
+    }
+    role == Role::RoleAdmin
+        && admin::read_admin(env).is_ok_and(|admin| admin == *account)
+}
+
+/// Asigna `role` a `account`
+pub fn grant(env: &Env, role: Role, account: &Address) {
+    let key = DataKey::Role(role, account.clone());
+    env.storage().persistent().set(&key, &true);
+    storage::extend_persistent(env, &key);
+}
+
+/// Quita `role` a `account` (no falla si no lo tenía)
+pub fn revoke(env: &Env, role: Role, account: &Address) {
+    env.storage().persistent().remove(&DataKey::Role(role, account.clone()));
+}
+
+/// Mueve todos los roles explícitos de `from` a `to`
+///
+/// Se usa en el traspaso de admin: la cuenta anterior (quizás una
+/// clave comprometida) no conserva ningún poder. Devuelve los roles
+/// movidos para emitir sus eventos
+pub fn transfer_all(env: &Env, from: &Address, to: &Address) -> Vec<Role> {
+    let mut moved = Vec::new(env);
+    for role in ALL_ROLES.into_iter().chain(OPTIONAL_ROLES) {
+        if env.storage().persistent().has(&DataKey::Role(role, from.clone())) {
+            revoke(env, role, from);
+            grant(env, role, to);
+            moved.push_back(role);
+        }
+    }
+    moved
+}
+
+/// Exige la firma de `account` y que tenga `role`
+pub fn require_role(env: &Env, role: Role, account: &Address) -> Result<(), TokenError> {
+    account.require_auth();
+    if !has_role(env, role, account) {
+        return Err(TokenError::Unauthorized);
+    }
+    Ok(())
+}
@@ -1,5 +1,5 @@
 // src/storage.rs
-use soroban_sdk::{contracttype, Address, String};
+use soroban_sdk::{contracttype, Address, BytesN, Env, String, Vec};
 
 /// Enum que define todas las claves de almacenamiento
 /// 
@@ -9,21 +9,54 @@ use soroban_sdk::{contracttype, Address, String};
 #[contracttype]
 pub enum DataKey {
     /// Balance de cada usuario - Persistent Storage
-    /// Usa Address como key para acceso O(1)
+    /// Usa Address como key para acceso O(1).
+    /// En modo rebasing guarda shares, no tokens
     Balance(Address),
     
-    /// Permisos de gasto entre usuarios - Persistent Storage
-    /// Tupla (owner, spender) para lookup eficiente
+    /// Permisos de gasto entre usuarios - Temporary Storage
+    /// Tupla (owner, spender) para lookup eficiente.
+    /// Guarda un AllowanceValue (amount + ledger de expiración) con TTL
+    /// hasta la expiración; en despliegues v0 puede quedar un i128
+    /// suelto en Persistent Storage (ver allowance.rs)
     Allowance(Address, Address),
     
     /// Supply total de tokens - Instance Storage
-    /// Contador global de tokens en circulación
+    /// Contador global de tokens en circulación.
+    /// En modo rebasing guarda el total de shares
     TotalSupply,
     
+    /// Tope opcional del supply total - Instance Storage
+    /// Sin key = sin tope. Solo puede bajar
+    MaxSupply,
+    
     /// Dirección del administrador - Instance Storage
     /// Solo esta cuenta puede mintear tokens
     Admin,
     
+    /// Admin propuesto pendiente de aceptar - Instance Storage
+    /// Guarda un PendingAdmin hasta accept_admin() o cancel
+    PendingAdmin,
+    
+    /// Cuota de mint de un minter - Persistent Storage
+    /// Guarda un MinterQuota; sin key = minter sin límite
+    MinterQuota(Address),
+    
+    /// Rol asignado a una cuenta - Persistent Storage
+    /// Tupla (rol, cuenta) -> true si la cuenta tiene el rol
+    Role(Role, Address),
+    
+    /// Flag de pausa de emergencia - Instance Storage
+    /// Si es true se bloquean transfers, mints y burns
+    Paused,
+    
+    /// Flag de autorización por cuenta - Persistent Storage
+    /// Solo se guarda false (cuenta congelada); sin key = autorizada
+    Authorized(Address),
+    
+    /// Si el token permite clawback - Instance Storage
+    /// Se fija en initialize() y nunca puede cambiar
+    ClawbackEnabled,
+    
     /// Nombre del token - Instance Storage
     /// Ejemplo: "Buen Día Builders Token"
     TokenName,
@@ -36,17 +69,296 @@ pub enum DataKey {
     /// Típicamente 7 para Stellar (alineado con XLM)
     Decimals,
     
+    /// Descripción del token - Instance Storage
+    /// Opcional: sin key = sin descripción
+    TokenDescription,
+    
+    /// URI del ícono / imagen del token - Instance Storage
+    /// Opcional (ej: "https://...", "ipfs://...")
+    TokenImageUri,
+    
+    /// Home domain SEP-1 - Instance Storage
+    /// Opcional: dominio que publica el stellar.toml del emisor
+    HomeDomain,
+    
+    /// Sitio web del proyecto - Instance Storage
+    /// Opcional
+    TokenWebsite,
+    
+    /// Versión del layout de storage - Instance Storage
+    /// Sin key = despliegue anterior al versionado (versión 0).
+    /// migrate() la lleva hasta CURRENT_SCHEMA_VERSION
+    SchemaVersion,
+    
+    /// Vencimiento de los allowances v0 (i128 sin expiración) - Instance Storage
+    /// Lo fija migrate() al migrar desde v0; sin key = no hay allowances v0
+    LegacyAllowanceExpiration,
+    
+    /// Política de TTL configurada por el admin - Instance Storage
+    /// Sin key = DEFAULT_TTL_CONFIG
+    TtlConfig,
+    
+    /// Checkpoints vigentes de una serie (balances, supply, votos o
+    /// índice de rebase) - Persistent Storage
+    /// Guarda un CheckpointRange; sin key = serie vacía
+    Checkpoints(CheckpointSeries),
+    
+    /// Checkpoint `i` de una serie - Persistent Storage
+    /// Una entrada por checkpoint, ordenadas por ledger (uno por ledger
+    /// como máximo): el historial no choca con el tope de tamaño de
+    /// una entrada
+    CheckpointAt(CheckpointSeries, u32),
+    
+    /// Delegado de los votos de una cuenta - Persistent Storage
+    /// Sin key = la cuenta no delegó (su balance no cuenta como votos)
+    Delegate(Address),
+    
+    /// Próximo nonce de permit() de una cuenta - Persistent Storage
+    /// Sin key = 0. Cada permit válido lo incrementa
+    Nonce(Address),
+    
+    /// Fee de transferencia configurada - Instance Storage
+    /// Guarda un FeeConfig; sin key = sin fee
+    FeeConfig,
+    
+    /// Cuenta exenta del fee de transferencia - Persistent Storage
+    /// Solo se guarda true; sin key = paga fee
+    FeeExempt(Address),
+    
+    /// Ledger desde el que se garantiza el historial - Instance Storage
+    /// Las consultas anteriores fallan con HistoryPruned. Solo sube
+    CheckpointHorizon,
+    
+    /// Si el token se creó en modo rebasing - Instance Storage
+    /// Solo se define en initialize(); sin key = modo estándar
+    Rebasing,
+    
+    /// Índice de rebase (tokens por share × INDEX_SCALE) - Instance Storage
+    /// Sin key = INDEX_SCALE (1 share = 1 token)
+    RebaseIndex,
+
+    
+    /// Cantidad de holders (balance distinto de 0) - Instance Storage
+    HolderCount,
+    
+    /// Próximo slot del registro de holders - Instance Storage
+    /// Solo crece: los slots liberados no se reutilizan
+    HolderSlots,
+    
+    /// Holder guardado en un slot - Persistent Storage
+    /// Se elimina cuando el balance del holder vuelve a 0
+    HolderAt(u32),
+    
+    /// Slot de una cuenta en el registro - Persistent Storage
+    /// Sin key = la cuenta no es holder
+    HolderSlot(Address),
+    
     /// Flag para verificar inicialización - Instance Storage
     /// Previene re-inicialización del contrato
     Initialized,
 }
 
-/// Metadata struct para almacenar información del token
-/// Usado en initialize() para pasar múltiples parámetros
+/// Roles de acceso del token
+/// 
+/// Cada función privilegiada exige un rol concreto en vez de
+/// comparar contra una única dirección de admin
 #[contracttype]
-#[derive(Clone)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[allow(clippy::enum_variant_names)]
+pub enum Role {
+    /// Puede crear tokens con mint()
+    Minter,
+    /// Puede pausar y despausar el contrato
+    Pauser,
+    /// Puede congelar cuentas individuales
+    Freezer,
+    /// Puede actualizar los metadatos del token
+    MetadataManager,
+    /// Puede otorgar y revocar roles
+    RoleAdmin,
+    /// Puede ejecutar clawback() (si el token lo tiene habilitado)
+    Clawback,
+    /// Puede publicar el índice con rebase() (si el token es rebasing)
+    Oracle,
+}
+
+/// Cuota de mint que se recarga cada período
+/// 
+/// Ejemplo: limit = 10_000 BDB, period_ledgers = 17_280 (~1 día).
+/// Cuando pasa el período, `used` vuelve a 0 y la ventana arranca
+/// en el ledger del siguiente mint
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MinterQuota {
+    pub limit: i128,
+    pub period_ledgers: u32,
+    pub window_start: u32,
+    pub used: i128,
+}
+
+/// Política de rent: cuándo y cuánto se extiende el TTL
+/// 
+/// `*_threshold`: si el TTL restante baja de este valor se extiende.
+/// `*_extend_to`: TTL (en ledgers) que queda después de extender
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TtlConfig {
+    pub instance_threshold: u32,
+    pub instance_extend_to: u32,
+    pub persistent_threshold: u32,
+    pub persistent_extend_to: u32,
+}
+
+/// Política por defecto: a 5s por ledger, 200_000 ledgers son ~11.6 días
+/// y se renueva cuando quedan menos de ~5.8 días
+pub const DEFAULT_TTL_CONFIG: TtlConfig = TtlConfig {
+    instance_threshold: 100_000,
+    instance_extend_to: 200_000,
+    persistent_threshold: 100_000,
+    persistent_extend_to: 200_000,
+};
+
+/// Lee la política de TTL vigente
+pub fn read_ttl_config(env: &Env) -> TtlConfig {
+    env.storage().instance()
+        .get(&DataKey::TtlConfig)
+        .unwrap_or(DEFAULT_TTL_CONFIG)
+}
+
+/// Extiende el TTL de una entrada persistente según la política
+/// 
+/// Único lugar donde se decide el TTL de balances, roles, cuotas, etc.
+pub fn extend_persistent(env: &Env, key: &DataKey) {
+    let config = read_ttl_config(env);
+    env.storage().persistent().extend_ttl(
+        key,
+        config.persistent_threshold,
+        config.persistent_extend_to
+    );
+}
+
+/// Extiende el TTL del instance storage (metadata, admin, supply)
+pub fn extend_instance(env: &Env) {
+    let config = read_ttl_config(env);
+    env.storage().instance().extend_ttl(
+        config.instance_threshold,
+        config.instance_extend_to
+    );
+}
+
+/// Metadata completa del token
+/// 
+/// La devuelve metadata() y viaja en los eventos "init" y "meta_upd".
+/// name, symbol y los campos opcionales se cambian con set_metadata();
+/// decimals es fijo
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TokenMetadata {
     pub name: String,
     pub symbol: String,
     pub decimals: u32,
-}
\ No newline at end of file
+    pub description: Option<String>,
+    pub image_uri: Option<String>,
+    /// Dominio SEP-1 (sin "https://"), ej: "buendiabuilders.com"
+    pub home_domain: Option<String>,
+    pub website: Option<String>,
+}
+
+/// Valor guardado en DataKey::Allowance
+/// 
+/// El allowance deja de valer cuando el ledger actual supera
+/// `expiration_ledger`; a partir de ahí se trata como 0
+#[contracttype]
+#[derive(Clone)]
+pub struct AllowanceValue {
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+/// Propuesta de traspaso de admin (two-step handover)
+/// 
+/// `deadline_ledger` opcional: pasado ese ledger la propuesta
+/// ya no puede aceptarse
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingAdmin {
+    pub admin: Address,
+    pub deadline_ledger: Option<u32>,
+}
+
+/// Página de holders()
+/// 
+/// `holders` puede tener menos de `limit` cuentas o ninguna (slots
+/// liberados); `next`: `start` de la página siguiente (None = no hay más)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HolderPage {
+    pub holders: Vec<Address>,
+    pub next: Option<u32>,
+}
+
+/// Historial con checkpoints (ver checkpoints.rs)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CheckpointSeries {
+    /// Balances de una cuenta (shares en modo rebasing)
+    Balance(Address),
+    /// Supply total (total de shares en modo rebasing)
+    Supply,
+    /// Votos de un delegado; el último es get_votes()
+    Votes(Address),
+    /// Índice de rebase; balance_at convierte shares con el de cada ledger
+    RebaseIndex,
+}
+
+/// Checkpoints vigentes de una serie: índices `start..end`
+/// 
+/// La poda sube `start` y borra esas entradas; los índices nunca se
+/// reutilizan
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CheckpointRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+/// Valor de un balance (o del supply) a partir de un ledger
+/// 
+/// Vale desde `ledger` hasta el ledger del checkpoint siguiente
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Checkpoint {
+    pub ledger: u32,
+    pub value: i128,
+}
+
+/// Mensaje que firma el owner para permit()
+/// 
+/// Se firma el XDR de esta estructura. `contract` y `network_id`
+/// (sha256 de la passphrase de la red) separan el dominio: una firma
+/// para BDB en testnet no sirve en mainnet ni en otro token.
+/// `nonce` evita que la misma firma se use dos veces y
+/// `deadline_ledger` que una firma sin usar quede válida para siempre
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PermitPayload {
+    pub contract: Address,
+    pub network_id: BytesN<32>,
+    pub owner: Address,
+    pub spender: Address,
+    pub amount: i128,
+    pub expiration_ledger: u32,
+    pub nonce: u64,
+    pub deadline_ledger: u32,
+}
+
+/// Fee de transferencia en basis points (1 bp = 0.01%)
+/// 
+/// El fee se descuenta del monto enviado: `to` recibe amount - fee
+/// y `treasury` recibe el fee
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeConfig {
+    pub fee_bps: u32,
+    pub treasury: Address,
+}
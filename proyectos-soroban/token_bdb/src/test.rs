@@ -2,9 +2,13 @@
 #![cfg(test)]
 
 use super::*;
+use crate::events::*;
 use soroban_sdk::{
-    testutils::{Address as _, Events},
-    Address, Env, String, symbol_short,
+    testutils::{
+        storage::{Instance as _, Persistent as _, Temporary as _},
+        Address as _, Events, Ledger,
+    },
+    symbol_short, vec, Address, Env, Event, IntoVal, String, Symbol, Val, Vec,
 };
 
 /// Test básico de inicialización del token
@@ -17,7 +21,7 @@ use soroban_sdk::{
 fn test_initialize() {
     // Arrange: Setup del entorno de testing
     let env = Env::default();
-    let contract_id = env.register_contract(None, TokenBDB);
+    let contract_id = env.register(TokenBDB, ());
     let client = TokenBDBClient::new(&env, &contract_id);
     
     let admin = Address::generate(&env);
@@ -25,8 +29,7 @@ fn test_initialize() {
     let symbol = String::from_str(&env, "BDB");
     
     // Act: Inicializar el token
-    let result = client.initialize(&admin, &name, &symbol, &7);
-    assert!(result.is_ok());
+    client.initialize(&admin, &name, &symbol, &7, &false, &None, &false);
     
     // Assert: Verificar que los metadatos se guardaron correctamente
     assert_eq!(client.name(), name);
@@ -42,7 +45,7 @@ fn test_initialize() {
 #[test]
 fn test_initialize_twice_fails() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, TokenBDB);
+    let contract_id = env.register(TokenBDB, ());
     let client = TokenBDBClient::new(&env, &contract_id);
     
     let admin = Address::generate(&env);
@@ -50,10 +53,10 @@ fn test_initialize_twice_fails() {
     let symbol = String::from_str(&env, "TOK");
     
     // Primera inicialización debe funcionar
-    assert!(client.initialize(&admin, &name, &symbol, &7).is_ok());
+    client.initialize(&admin, &name, &symbol, &7, &false, &None, &false);
     
     // Segunda debe fallar con AlreadyInitialized
-    let result = client.try_initialize(&admin, &name, &symbol, &7);
+    let result = client.try_initialize(&admin, &name, &symbol, &7, &false, &None, &false);
     assert_eq!(result, Err(Ok(TokenError::AlreadyInitialized)));
 }
 
@@ -66,7 +69,7 @@ fn test_initialize_twice_fails() {
 #[test]
 fn test_invalid_decimals() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, TokenBDB);
+    let contract_id = env.register(TokenBDB, ());
     let client = TokenBDBClient::new(&env, &contract_id);
     
     let admin = Address::generate(&env);
@@ -76,7 +79,10 @@ fn test_invalid_decimals() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &19  // ❌ Inválido: excede MAX_DECIMALS (18)
+        &19,  // ❌ Inválido: excede MAX_DECIMALS (18)
+        &false,
+        &None,
+        &false
     );
     assert_eq!(result, Err(Ok(TokenError::InvalidDecimals)));
 }
@@ -92,7 +98,7 @@ fn test_invalid_decimals() {
 #[test]
 fn test_mint_and_balance() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, TokenBDB);
+    let contract_id = env.register(TokenBDB, ());
     let client = TokenBDBClient::new(&env, &contract_id);
     
     let admin = Address::generate(&env);
@@ -103,14 +109,17 @@ fn test_mint_and_balance() {
         &admin, 
         &String::from_str(&env, "Builder Token"),
         &String::from_str(&env, "BDB"),
-        &7
-    ).unwrap();
+        &7,
+        &false,
+        &None,
+        &false
+    );
     
     // Mock auth: En tests, simulamos autorizaciones sin firmas reales
     env.mock_all_auths();
     
     // Mintear 1000 tokens
-    client.mint(&user, &1000).unwrap();
+    client.mint(&admin, &user, &1000);
     
     // Verificar estado actualizado
     assert_eq!(client.balance(&user), 1000);
@@ -124,7 +133,7 @@ fn test_mint_and_balance() {
 #[test]
 fn test_mint_zero_fails() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, TokenBDB);
+    let contract_id = env.register(TokenBDB, ());
     let client = TokenBDBClient::new(&env, &contract_id);
     
     let admin = Address::generate(&env);
@@ -134,13 +143,16 @@ fn test_mint_zero_fails() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
-    ).unwrap();
+        &7,
+        &false,
+        &None,
+        &false
+    );
     
     env.mock_all_auths();
     
     // Mintear 0 debe fallar con InvalidAmount
-    let result = client.try_mint(&user, &0);
+    let result = client.try_mint(&admin, &user, &0);
     assert_eq!(result, Err(Ok(TokenError::InvalidAmount)));
 }
 
@@ -153,7 +165,7 @@ fn test_mint_zero_fails() {
 #[test]
 fn test_transfer() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, TokenBDB);
+    let contract_id = env.register(TokenBDB, ());
     let client = TokenBDBClient::new(&env, &contract_id);
     
     let admin = Address::generate(&env);
@@ -165,14 +177,17 @@ fn test_transfer() {
         &admin,
         &String::from_str(&env, "Builder Token"),
         &String::from_str(&env, "BDB"),
-        &7
-    ).unwrap();
+        &7,
+        &false,
+        &None,
+        &false
+    );
     
     env.mock_all_auths();
-    client.mint(&alice, &1000).unwrap();
+    client.mint(&admin, &alice, &1000);
     
     // Act: Alice transfiere a Bob
-    client.transfer(&alice, &bob, &250).unwrap();
+    client.transfer(&alice, &bob, &250);
     
     // Assert: Verificar ambos balances
     assert_eq!(client.balance(&alice), 750);  // 1000 - 250
@@ -187,7 +202,7 @@ fn test_transfer() {
 #[test]
 fn test_transfer_insufficient_balance() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, TokenBDB);
+    let contract_id = env.register(TokenBDB, ());
     let client = TokenBDBClient::new(&env, &contract_id);
     
     let admin = Address::generate(&env);
@@ -198,15 +213,18 @@ fn test_transfer_insufficient_balance() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
-    ).unwrap();
+        &7,
+        &false,
+        &None,
+        &false
+    );
     
     env.mock_all_auths();
-    client.mint(&alice, &100).unwrap();
+    client.mint(&admin, &alice, &100);
     
     // Intentar transferir más de lo que tiene debe fallar
     let result = client.try_transfer(&alice, &bob, &200);
-    assert_eq!(result, Err(Ok(TokenError::InsufficientBalance)));
+    assert_eq!(result, Err(Ok(TokenError::InsufficientBalance.into())));
 }
 
 
@@ -219,7 +237,7 @@ fn test_transfer_insufficient_balance() {
 #[test]
 fn test_transfer_to_self() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, TokenBDB);
+    let contract_id = env.register(TokenBDB, ());
     let client = TokenBDBClient::new(&env, &contract_id);
     
     let admin = Address::generate(&env);
@@ -229,15 +247,18 @@ fn test_transfer_to_self() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
-    ).unwrap();
+        &7,
+        &false,
+        &None,
+        &false
+    );
     
     env.mock_all_auths();
-    client.mint(&alice, &1000).unwrap();
+    client.mint(&admin, &alice, &1000);
     
     // Transfer a sí mismo debe fallar con InvalidRecipient
     let result = client.try_transfer(&alice, &alice, &100);
-    assert_eq!(result, Err(Ok(TokenError::InvalidRecipient)));
+    assert_eq!(result, Err(Ok(TokenError::InvalidRecipient.into())));
     assert_eq!(client.balance(&alice), 1000); // Balance no debe cambiar
 }
 
@@ -251,7 +272,7 @@ fn test_transfer_to_self() {
 #[test]
 fn test_approve_and_transfer_from() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, TokenBDB);
+    let contract_id = env.register(TokenBDB, ());
     let client = TokenBDBClient::new(&env, &contract_id);
     
     let admin = Address::generate(&env);
@@ -264,18 +285,21 @@ fn test_approve_and_transfer_from() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
-    ).unwrap();
+        &7,
+        &false,
+        &None,
+        &false
+    );
     
     env.mock_all_auths();
-    client.mint(&alice, &1000).unwrap();
+    client.mint(&admin, &alice, &1000);
     
     // Alice aprueba a Bob para gastar hasta 300 tokens
-    client.approve(&alice, &bob, &300).unwrap();
+    client.approve(&alice, &bob, &300, &1000);
     assert_eq!(client.allowance(&alice, &bob), 300);
     
     // Bob transfiere 200 tokens de Alice a Charlie
-    client.transfer_from(&bob, &alice, &charlie, &200).unwrap();
+    client.transfer_from(&bob, &alice, &charlie, &200);
     
     // Verificar estado final
     assert_eq!(client.balance(&alice), 800);          // 1000 - 200
@@ -289,7 +313,7 @@ fn test_approve_and_transfer_from() {
 #[test]
 fn test_transfer_from_insufficient_allowance() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, TokenBDB);
+    let contract_id = env.register(TokenBDB, ());
     let client = TokenBDBClient::new(&env, &contract_id);
     
     let admin = Address::generate(&env);
@@ -301,16 +325,19 @@ fn test_transfer_from_insufficient_allowance() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
-    ).unwrap();
+        &7,
+        &false,
+        &None,
+        &false
+    );
     
     env.mock_all_auths();
-    client.mint(&alice, &1000).unwrap();
-    client.approve(&alice, &bob, &100).unwrap();  // Solo 100 aprobados
+    client.mint(&admin, &alice, &1000);
+    client.approve(&alice, &bob, &100, &1000);  // Solo 100 aprobados
     
     // Bob intenta transferir más de lo aprobado
     let result = client.try_transfer_from(&bob, &alice, &charlie, &200);
-    assert_eq!(result, Err(Ok(TokenError::InsufficientAllowance)));
+    assert_eq!(result, Err(Ok(TokenError::InsufficientAllowance.into())));
 }
 
 /// Test básico de burn (quemar tokens)
@@ -320,7 +347,7 @@ fn test_transfer_from_insufficient_allowance() {
 #[test]
 fn test_burn() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, TokenBDB);
+    let contract_id = env.register(TokenBDB, ());
     let client = TokenBDBClient::new(&env, &contract_id);
     
     let admin = Address::generate(&env);
@@ -330,14 +357,17 @@ fn test_burn() {
         &admin,
         &String::from_str(&env, "Token"),
         &String::from_str(&env, "TOK"),
-        &7
-    ).unwrap();
+        &7,
+        &false,
+        &None,
+        &false
+    );
     
     env.mock_all_auths();
-    client.mint(&alice, &1000).unwrap();
+    client.mint(&admin, &alice, &1000);
     
     // Alice quema 300 de sus tokens
-    client.burn(&alice, &300).unwrap();
+    client.burn(&alice, &300);
     
     // Verificar que tanto balance como supply se redujeron
     assert_eq!(client.balance(&alice), 700);    // 1000 - 300
@@ -352,7 +382,7 @@ fn test_burn() {
 #[test]
 fn test_operations_without_init() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, TokenBDB);
+    let contract_id = env.register(TokenBDB, ());
     let client = TokenBDBClient::new(&env, &contract_id);
     
     let alice = Address::generate(&env);
@@ -362,28 +392,3033 @@ fn test_operations_without_init() {
     
     // Todas las operaciones deben fallar con NotInitialized
     assert_eq!(
-        client.try_mint(&alice, &100),
+        client.try_mint(&alice, &alice, &100),
         Err(Ok(TokenError::NotInitialized))
     );
     
     assert_eq!(
         client.try_transfer(&alice, &bob, &50),
-        Err(Ok(TokenError::NotInitialized))
+        Err(Ok(TokenError::NotInitialized.into()))
     );
     
     assert_eq!(
         client.try_burn(&alice, &10),
-        Err(Ok(TokenError::NotInitialized))
+        Err(Ok(TokenError::NotInitialized.into()))
+    );
+}
+
+/// Test: un allowance vencido vale 0 y no se puede gastar
+/// 
+/// Alice aprueba a Bob hasta el ledger 100. Pasado ese ledger la
+/// entrada temporal ya no existe: allowance() devuelve 0 y
+/// transfer_from falla como sin approve (InsufficientAllowance).
+#[test]
+fn test_allowance_expires() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
     );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    client.approve(&alice, &bob, &300, &100);
+    
+    // En el ledger de expiración todavía es válido
+    env.ledger().set_sequence_number(100);
+    assert_eq!(client.allowance(&alice, &bob), 300);
+    
+    // Un ledger después ya expiró
+    env.ledger().set_sequence_number(101);
+    assert_eq!(client.allowance(&alice, &bob), 0);
+    assert!(!env.as_contract(&contract_id, || {
+        env.storage().temporary().has(&DataKey::Allowance(alice.clone(), bob.clone()))
+    }));
+    
+    let result = client.try_transfer_from(&bob, &alice, &charlie, &100);
+    assert_eq!(result, Err(Ok(TokenError::InsufficientAllowance.into())));
+    assert_eq!(client.balance(&alice), 1000);
 }
 
+/// Test: approve con expiration_ledger en el pasado, o más lejos de
+/// lo que puede vivir una entrada temporal, debe fallar
+/// 
+/// Revocar (amount = 0) sí se permite con cualquier ledger.
+#[test]
+fn test_approve_past_expiration_fails() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    env.ledger().set_sequence_number(50);
+    
+    let result = client.try_approve(&alice, &bob, &100, &49);
+    assert_eq!(result, Err(Ok(TokenError::InvalidExpirationLedger.into())));
+    
+    let too_far = env.ledger().max_live_until_ledger() + 1;
+    let result = client.try_approve(&alice, &bob, &100, &too_far);
+    assert_eq!(result, Err(Ok(TokenError::InvalidExpirationLedger.into())));
+    client.approve(&alice, &bob, &100, &(too_far - 1));
+    assert_eq!(client.allowance(&alice, &bob), 100);
+    
+    // Revocar con un ledger pasado es válido
+    client.approve(&alice, &bob, &0, &0);
+    assert_eq!(client.allowance(&alice, &bob), 0);
+}
 
+/// Test: el TTL de la entrada de allowance sigue a su expiración
+/// 
+/// Así un approve vencido se archiva solo y no sigue pagando rent.
+#[test]
+fn test_allowance_ttl_follows_expiration() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    env.ledger().set_sequence_number(1_000);
+    client.approve(&alice, &bob, &500, &51_000);
+    
+    let ttl = env.as_contract(&contract_id, || {
+        env.storage().temporary().get_ttl(&DataKey::Allowance(alice.clone(), bob.clone()))
+    });
+    assert_eq!(ttl, 50_000);
+}
 
+/// Test de burn_from: un contrato de redención quema tokens aprobados
+/// 
+/// Verifica que:
+/// - Se consume el allowance y se elimina al llegar a 0
+/// - Baja el balance del holder y el supply total
+/// - El evento identifica al spender que ejecutó la quema
+#[test]
+fn test_burn_from() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let redeemer = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    client.approve(&alice, &redeemer, &400, &1000);
+    
+    // El redeemer quema 150 y luego el resto del allowance
+    client.burn_from(&redeemer, &alice, &150);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("burn"), alice.clone()).into_val(&env),
+                150i128.into_val(&env)
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("burn_from"), alice.clone(), redeemer.clone(), EVENT_SCHEMA_VERSION).into_val(&env),
+                BurnFromEvent {
+                    from: alice.clone(),
+                    spender: redeemer.clone(),
+                    amount: 150,
+                    version: EVENT_SCHEMA_VERSION,
+                }.data(&env)
+            )
+        ]
+    );
+    assert_eq!(client.allowance(&alice, &redeemer), 250);
+    
+    client.burn_from(&redeemer, &alice, &250);
+    assert_eq!(client.balance(&alice), 600);          // 1000 - 400
+    assert_eq!(client.total_supply(), 600);
+    
+    // El allowance en 0 se elimina del storage
+    let has_allowance = env.as_contract(&contract_id, || {
+        env.storage().temporary().has(&DataKey::Allowance(alice.clone(), redeemer.clone()))
+    });
+    assert!(!has_allowance);
+}
 
+/// Test: burn_from requiere allowance suficiente
+/// 
+/// Sin approve el redeemer no puede quemar, y quemar todo el
+/// balance elimina la key del holder.
+#[test]
+fn test_burn_from_requires_allowance() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let redeemer = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &100);
+    
+    let result = client.try_burn_from(&redeemer, &alice, &50);
+    assert_eq!(result, Err(Ok(TokenError::InsufficientAllowance.into())));
+    
+    // Con allowance de sobra, el límite es el balance
+    client.approve(&alice, &redeemer, &500, &1000);
+    let result = client.try_burn_from(&redeemer, &alice, &101);
+    assert_eq!(result, Err(Ok(TokenError::InsufficientBalance.into())));
+    
+    client.burn_from(&redeemer, &alice, &100);
+    let has_balance = env.as_contract(&contract_id, || {
+        env.storage().persistent().has(&DataKey::Balance(alice.clone()))
+    });
+    assert!(!has_balance);
+    assert_eq!(client.total_supply(), 0);
+    assert_eq!(client.allowance(&alice, &redeemer), 400);
+}
 
+/// Test del traspaso de admin en dos pasos
+/// 
+/// 1. El admin propone a new_admin (el admin todavía no cambia)
+/// 2. new_admin acepta, recibe los roles del admin anterior y pasa
+///    a ser el único que puede mintear
+#[test]
+fn test_admin_handover() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    
+    // Paso 1: propuesta
+    client.propose_admin(&new_admin, &None);
+    assert_eq!(client.admin(), admin);
+    assert_eq!(
+        client.pending_admin(),
+        Some(PendingAdmin { admin: new_admin.clone(), deadline_ledger: None })
+    );
+    
+    // Paso 2: aceptación firmada por el admin propuesto
+    client.accept_admin();
+    assert_eq!(env.auths()[0].0, new_admin);
+    assert_eq!(client.admin(), new_admin);
+    assert_eq!(client.pending_admin(), None);
+    
+    // Los roles pasan al nuevo admin
+    for role in [Role::Minter, Role::Pauser, Role::Freezer, Role::MetadataManager, Role::RoleAdmin] {
+        assert!(client.has_role(&role, &new_admin));
+        assert!(!client.has_role(&role, &admin));
+    }
+    client.mint(&new_admin, &alice, &100);
+    assert_eq!(env.auths()[0].0, new_admin);
+    
+    // El admin anterior ya no puede mintear, pausar ni otorgar roles
+    assert_eq!(
+        client.try_mint(&admin, &alice, &100),
+        Err(Ok(TokenError::Unauthorized))
+    );
+    assert_eq!(client.try_pause(&admin), Err(Ok(TokenError::Unauthorized)));
+    assert_eq!(
+        client.try_grant_role(&admin, &Role::Minter, &admin),
+        Err(Ok(TokenError::Unauthorized))
+    );
+    assert_eq!(client.balance(&alice), 100);
+}
 
+/// Test: una propuesta con deadline no puede aceptarse después de vencer
+#[test]
+fn test_admin_proposal_deadline() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    env.ledger().set_sequence_number(10);
+    
+    // Un deadline en el pasado se rechaza directamente
+    let result = client.try_propose_admin(&new_admin, &Some(9));
+    assert_eq!(result, Err(Ok(TokenError::AdminProposalExpired)));
+    
+    client.propose_admin(&new_admin, &Some(100));
+    
+    env.ledger().set_sequence_number(101);
+    let result = client.try_accept_admin();
+    assert_eq!(result, Err(Ok(TokenError::AdminProposalExpired)));
+    assert_eq!(client.admin(), admin);
+}
 
+/// Test: cancelar la propuesta impide que se acepte
+#[test]
+fn test_cancel_admin_proposal() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let typo_admin = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    
+    // Sin propuesta no hay nada que cancelar ni aceptar
+    assert_eq!(client.try_cancel_admin_proposal(), Err(Ok(TokenError::NoPendingAdmin)));
+    
+    client.propose_admin(&typo_admin, &None);
+    client.cancel_admin_proposal();
+    assert_eq!(env.auths()[0].0, admin);
+    
+    assert_eq!(client.try_accept_admin(), Err(Ok(TokenError::NoPendingAdmin)));
+    assert_eq!(client.admin(), admin);
+}
 
+/// Test del sistema de roles
+/// 
+/// - El admin recibe todos los roles en initialize()
+/// - Un RoleAdmin puede delegar Minter a otra cuenta
+/// - Sin el rol, mint falla con Unauthorized
+#[test]
+fn test_roles_grant_and_revoke() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let minter = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    
+    assert!(client.has_role(&Role::Minter, &admin));
+    assert!(client.has_role(&Role::Pauser, &admin));
+    assert!(client.has_role(&Role::RoleAdmin, &admin));
+    assert!(!client.has_role(&Role::Minter, &minter));
+    
+    // Sin rol no puede mintear
+    assert_eq!(
+        client.try_mint(&minter, &alice, &100),
+        Err(Ok(TokenError::Unauthorized))
+    );
+    
+    // El admin delega Minter
+    client.grant_role(&admin, &Role::Minter, &minter);
+    client.mint(&minter, &alice, &100);
+    assert_eq!(env.auths()[0].0, minter);
+    assert_eq!(client.balance(&alice), 100);
+    
+    // Revocado, vuelve a fallar
+    client.revoke_role(&admin, &Role::Minter, &minter);
+    assert_eq!(
+        client.try_mint(&minter, &alice, &100),
+        Err(Ok(TokenError::Unauthorized))
+    );
+}
 
+/// Test: solo RoleAdmin puede otorgar roles y cualquiera puede renunciar
+#[test]
+fn test_roles_only_role_admin_grants() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let mallory = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    
+    // Una cuenta sin RoleAdmin no puede darse roles
+    assert_eq!(
+        client.try_grant_role(&mallory, &Role::Minter, &mallory),
+        Err(Ok(TokenError::Unauthorized))
+    );
+    
+    // El admin renuncia a Minter; RoleAdmin se conserva por ser el admin
+    client.renounce_role(&admin, &Role::Minter);
+    client.renounce_role(&admin, &Role::RoleAdmin);
+    assert!(!client.has_role(&Role::Minter, &admin));
+    assert!(client.has_role(&Role::RoleAdmin, &admin));
+}
 
+/// Test del modo pausa
+/// 
+/// Con el contrato pausado fallan transfer, transfer_from, mint y burn,
+/// pero balance y allowance se pueden seguir consultando.
+#[test]
+fn test_pause_blocks_movements() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    client.approve(&alice, &bob, &300, &1000);
+    
+    client.pause(&admin);
+    assert!(client.is_paused());
+    
+    // Movimientos bloqueados
+    assert_eq!(
+        client.try_transfer(&alice, &bob, &100),
+        Err(Ok(TokenError::ContractPaused.into()))
+    );
+    assert_eq!(
+        client.try_transfer_from(&bob, &alice, &bob, &100),
+        Err(Ok(TokenError::ContractPaused.into()))
+    );
+    assert_eq!(
+        client.try_mint(&admin, &alice, &100),
+        Err(Ok(TokenError::ContractPaused))
+    );
+    assert_eq!(
+        client.try_burn(&alice, &100),
+        Err(Ok(TokenError::ContractPaused.into()))
+    );
+    
+    // Consultas siguen funcionando
+    assert_eq!(client.balance(&alice), 1000);
+    assert_eq!(client.allowance(&alice, &bob), 300);
+    
+    // Al despausar todo vuelve a la normalidad
+    client.unpause(&admin);
+    assert!(!client.is_paused());
+    client.transfer(&alice, &bob, &100);
+    assert_eq!(client.balance(&bob), 100);
+}
 
+/// Test: solo una cuenta con rol Pauser puede pausar
+#[test]
+fn test_pause_requires_pauser_role() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let guardian = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    
+    assert_eq!(client.try_pause(&guardian), Err(Ok(TokenError::Unauthorized)));
+    
+    // Un guardian con rol Pauser puede reaccionar sin el admin
+    client.grant_role(&admin, &Role::Pauser, &guardian);
+    client.pause(&guardian);
+    assert_eq!(env.auths()[0].0, guardian);
+    assert!(client.is_paused());
+}
+
+/// Test de congelamiento de cuentas (set_authorized)
+/// 
+/// Una cuenta desautorizada no puede enviar, recibir, recibir mints
+/// ni quemar, pero su balance sigue visible. Al reautorizarla vuelve
+/// a operar.
+#[test]
+fn test_set_authorized_freezes_account() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    client.mint(&admin, &bob, &1000);
+    client.approve(&bob, &charlie, &500, &1000);
+    assert!(client.authorized(&alice));
+    
+    client.set_authorized(&admin, &alice, &false);
+    assert!(!client.authorized(&alice));
+    
+    // No puede enviar
+    assert_eq!(
+        client.try_transfer(&alice, &bob, &100),
+        Err(Ok(TokenError::AccountNotAuthorized.into()))
+    );
+    // Ni recibir, directo o vía transfer_from
+    assert_eq!(
+        client.try_transfer(&bob, &alice, &100),
+        Err(Ok(TokenError::AccountNotAuthorized.into()))
+    );
+    assert_eq!(
+        client.try_transfer_from(&charlie, &bob, &alice, &100),
+        Err(Ok(TokenError::AccountNotAuthorized.into()))
+    );
+    
+    // Ni recibir mints, sueltos o en lote
+    assert_eq!(
+        client.try_mint(&admin, &alice, &100),
+        Err(Ok(TokenError::AccountNotAuthorized))
+    );
+    assert_eq!(
+        client.try_batch_mint(&admin, &vec![&env, (bob.clone(), 100), (alice.clone(), 100)]),
+        Err(Ok(TokenError::AccountNotAuthorized))
+    );
+    // Ni quemar, directo o vía burn_from
+    client.approve(&alice, &charlie, &500, &1000);
+    assert_eq!(
+        client.try_burn(&alice, &100),
+        Err(Ok(TokenError::AccountNotAuthorized.into()))
+    );
+    assert_eq!(
+        client.try_burn_from(&charlie, &alice, &100),
+        Err(Ok(TokenError::AccountNotAuthorized.into()))
+    );
+    
+    // El balance sigue visible
+    assert_eq!(client.balance(&alice), 1000);
+    assert_eq!(client.total_supply(), 2000);
+    
+    // Reautorizada vuelve a operar
+    client.set_authorized(&admin, &alice, &true);
+    client.transfer(&alice, &bob, &100);
+    assert_eq!(client.balance(&bob), 1100);
+}
+
+/// Test: solo el rol Freezer puede congelar cuentas
+#[test]
+fn test_set_authorized_requires_freezer_role() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let compliance = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    
+    assert_eq!(
+        client.try_set_authorized(&compliance, &alice, &false),
+        Err(Ok(TokenError::Unauthorized))
+    );
+    
+    client.grant_role(&admin, &Role::Freezer, &compliance);
+    client.set_authorized(&compliance, &alice, &false);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (Symbol::new(&env, "set_authorized"), alice.clone()).into_val(&env),
+                false.into_val(&env)
+            )
+        ]
+    );
+    assert!(!client.authorized(&alice));
+}
+
+/// Test de clawback habilitado en initialize()
+/// 
+/// El admin retira tokens de Alice sin su firma: baja el balance
+/// y el supply total, y se emite el evento clawback.
+#[test]
+fn test_clawback() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &true,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    
+    client.clawback(&admin, &alice, &400);
+    assert_eq!(env.auths().len(), 1);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("clawback"), alice.clone()).into_val(&env),
+                400i128.into_val(&env)
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("clawbk_by"), admin.clone(), alice.clone(), EVENT_SCHEMA_VERSION).into_val(&env),
+                ClawbackCallerEvent {
+                    caller: admin.clone(),
+                    from: alice.clone(),
+                    amount: 400,
+                    version: EVENT_SCHEMA_VERSION,
+                }.data(&env)
+            )
+        ]
+    );
+    
+    assert_eq!(client.balance(&alice), 600);
+    assert_eq!(client.total_supply(), 600);
+}
+
+/// Test: sin clawback en initialize() nadie puede retirar tokens
+/// 
+/// Ni siquiera el admin ni una cuenta con rol Clawback.
+#[test]
+fn test_clawback_disabled() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    client.grant_role(&admin, &Role::Clawback, &admin);
+    
+    assert!(!client.clawback_enabled());
+    assert_eq!(
+        client.try_clawback(&admin, &alice, &100),
+        Err(Ok(TokenError::ClawbackDisabled))
+    );
+    assert_eq!(client.balance(&alice), 1000);
+}
+
+/// Test: el rol Clawback permite retirar sin ser admin
+#[test]
+fn test_clawback_role() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let enforcer = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &true,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    
+    assert_eq!(
+        client.try_clawback(&enforcer, &alice, &100),
+        Err(Ok(TokenError::Unauthorized))
+    );
+    
+    client.grant_role(&admin, &Role::Clawback, &enforcer);
+    client.clawback(&enforcer, &alice, &1000);
+    assert_eq!(client.balance(&alice), 0);
+    assert_eq!(client.total_supply(), 0);
+}
+
+/// Test del tope de supply (max_supply)
+/// 
+/// Con un tope de 1000, se puede mintear hasta 1000 exactos
+/// y cualquier mint extra falla con SupplyCapExceeded.
+#[test]
+fn test_max_supply_enforced_in_mint() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &Some(1000),
+        &false
+    );
+    
+    env.mock_all_auths();
+    assert_eq!(client.max_supply(), Some(1000));
+    
+    client.mint(&admin, &alice, &600);
+    client.mint(&admin, &alice, &400);
+    assert_eq!(client.total_supply(), 1000);
+    
+    assert_eq!(
+        client.try_mint(&admin, &alice, &1),
+        Err(Ok(TokenError::SupplyCapExceeded))
+    );
+    
+    // Quemar libera espacio bajo el tope
+    client.burn(&alice, &100);
+    client.mint(&admin, &alice, &100);
+    assert_eq!(client.total_supply(), 1000);
+}
+
+/// Test: el tope solo puede bajar y nunca por debajo del supply emitido
+#[test]
+fn test_set_max_supply_only_lowers() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    assert_eq!(client.max_supply(), None);
+    client.mint(&admin, &alice, &500);
+    
+    // Sin tope se puede definir uno (equivale a bajar desde infinito)
+    client.set_max_supply(&2000);
+    assert_eq!(client.max_supply(), Some(2000));
+    
+    // Subirlo no está permitido
+    assert_eq!(client.try_set_max_supply(&3000), Err(Ok(TokenError::InvalidSupplyCap)));
+    
+    // Ni bajarlo por debajo de lo emitido
+    assert_eq!(client.try_set_max_supply(&499), Err(Ok(TokenError::InvalidSupplyCap)));
+    
+    client.set_max_supply(&500);
+    assert_eq!(client.max_supply(), Some(500));
+    assert_eq!(
+        client.try_mint(&admin, &alice, &1),
+        Err(Ok(TokenError::SupplyCapExceeded))
+    );
+}
+
+/// Test de cuotas de mint por período
+/// 
+/// Un minter con cuota de 10_000 cada 17_280 ledgers:
+/// - Puede mintear hasta 10_000 en la ventana
+/// - El evento informa cuánto le queda
+/// - Al terminar el período la cuota se recarga
+#[test]
+fn test_minter_quota_per_period() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let minter = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    env.ledger().set_sequence_number(100);
+    client.grant_role(&admin, &Role::Minter, &minter);
+    client.set_minter_quota(&admin, &minter, &10_000, &17_280);
+    
+    client.mint(&minter, &alice, &6_000);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("mint"), alice.clone()).into_val(&env),
+                6_000i128.into_val(&env)
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("mint_qta"), minter.clone(), alice.clone(), EVENT_SCHEMA_VERSION).into_val(&env),
+                MintQuotaEvent {
+                    minter: minter.clone(),
+                    to: alice.clone(),
+                    amount: 6_000,
+                    remaining: 4_000,
+                    version: EVENT_SCHEMA_VERSION,
+                }.data(&env)
+            )
+        ]
+    );
+    
+    // Pasarse de la cuota falla
+    assert_eq!(
+        client.try_mint(&minter, &alice, &4_001),
+        Err(Ok(TokenError::MinterQuotaExceeded))
+    );
+    client.mint(&minter, &alice, &4_000);
+    assert_eq!(client.minter_quota(&minter).unwrap().used, 10_000);
+    
+    // Un ledger antes del fin de la ventana sigue agotada
+    env.ledger().set_sequence_number(100 + 17_279);
+    assert_eq!(
+        client.try_mint(&minter, &alice, &1),
+        Err(Ok(TokenError::MinterQuotaExceeded))
+    );
+    
+    // Nueva ventana: la cuota se recarga
+    env.ledger().set_sequence_number(100 + 17_280);
+    assert_eq!(client.minter_quota(&minter).unwrap().used, 0);
+    client.mint(&minter, &alice, &10_000);
+    assert_eq!(client.balance(&alice), 20_000);
+}
+
+/// Test: varios minters con cuotas independientes y minters sin cuota
+#[test]
+fn test_minter_quota_independent_minters() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let minter_a = Address::generate(&env);
+    let minter_b = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    client.grant_role(&admin, &Role::Minter, &minter_a);
+    client.grant_role(&admin, &Role::Minter, &minter_b);
+    client.set_minter_quota(&admin, &minter_a, &100, &1_000);
+    client.set_minter_quota(&admin, &minter_b, &500, &1_000);
+    
+    client.mint(&minter_a, &alice, &100);
+    client.mint(&minter_b, &alice, &500);
+    assert_eq!(
+        client.try_mint(&minter_a, &alice, &1),
+        Err(Ok(TokenError::MinterQuotaExceeded))
+    );
+    
+    // El admin no tiene cuota: sin límite
+    assert_eq!(client.minter_quota(&admin), None);
+    client.mint(&admin, &alice, &1_000_000);
+    
+    // Quitar la cuota deja al minter sin límite
+    client.remove_minter_quota(&admin, &minter_a);
+    client.mint(&minter_a, &alice, &1_000);
+    
+    // Cuota inválida
+    assert_eq!(
+        client.try_set_minter_quota(&admin, &minter_b, &100, &0),
+        Err(Ok(TokenError::InvalidQuota))
+    );
+}
+
+/// Builds anteriores y actual como wasm reales (ver testdata/ y Makefile)
+mod v0 {
+    soroban_sdk::contractimport!(file = "testdata/token_bdb_v0.wasm");
+}
+mod v1 {
+    soroban_sdk::contractimport!(file = "testdata/token_bdb_v1.wasm");
+}
+const CURRENT_WASM: &[u8] = include_bytes!("../testdata/token_bdb.wasm");
+
+/// Test de upgrade + migrate entre dos builds reales
+/// 
+/// Despliega el wasm v1 (primer build con upgrade), sube el wasm
+/// actual y llama a upgrade(hash) sobre el contrato desplegado:
+/// - Balances y allowances sobreviven al cambio de código
+/// - migrate() corre v1 -> v2 una sola vez
+/// - v1 -> v2 fija el horizonte de checkpoints en el ledger del upgrade
+#[test]
+fn test_upgrade_from_v1_wasm_keeps_state_and_migrates() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(v1::WASM, ());
+    let old = v1::Client::new(&env, &contract_id);
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    old.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None
+    );
+    old.mint(&admin, &alice, &1000);
+    old.approve(&alice, &bob, &300, &1000);
+    assert_eq!(old.schema_version(), 1);
+    
+    // Nuevo build en la misma dirección, vía upgrade()
+    let new_wasm_hash = env.deployer().upload_contract_wasm(CURRENT_WASM);
+    client.upgrade(&new_wasm_hash);
+    
+    // El estado de usuarios sobrevive
+    assert_eq!(client.balance(&alice), 1000);
+    assert_eq!(client.allowance(&alice, &bob), 300);
+    assert_eq!(client.total_supply(), 1000);
+    assert_eq!(client.schema_version(), 1);
+    
+    // migrate() corre v1 -> v2 exactamente una vez
+    assert_eq!(client.migrate(), upgrade::CURRENT_SCHEMA_VERSION);
+    assert_eq!(client.try_migrate(), Err(Ok(TokenError::AlreadyMigrated)));
+    assert_eq!(client.checkpoint_horizon(), env.ledger().sequence());
+    
+    client.mint(&admin, &alice, &1);
+    client.transfer_from(&bob, &alice, &bob, &100);
+    assert_eq!(client.balance(&alice), 901);
+    assert_eq!(client.allowance(&alice, &bob), 200);
+}
+
+/// Test de migración desde el build v0 (anterior al versionado)
+/// 
+/// v0 no tiene upgrade(), así que el test hace desde adentro del
+/// contrato lo mismo que hace upgrade(). El storage es el que escribió el wasm v0 real: sin
+/// SchemaVersion, sin roles y con allowances guardados como i128:
+/// - Sin migrar, el admin no tiene rol Minter y el allowance legacy
+///   no tiene vencimiento, así que no se puede gastar
+/// - migrate() corre v0 -> v1 -> v2, el admin vuelve a mintear y el
+///   allowance legacy vale hasta LEGACY_ALLOWANCE_LEDGERS después
+/// - El primer gasto lo pasa a temporary storage con ese vencimiento
+#[test]
+fn test_migrate_from_v0_wasm_reads_legacy_allowances() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(v0::WASM, ());
+    let old = v0::Client::new(&env, &contract_id);
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    old.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7
+    );
+    old.mint(&alice, &1000);
+    old.approve(&alice, &bob, &300);
+    
+    let new_wasm_hash = env.deployer().upload_contract_wasm(CURRENT_WASM);
+    env.as_contract(&contract_id, || {
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    });
+    assert_eq!(client.schema_version(), 0);
+    assert_eq!(client.balance(&alice), 1000);
+    assert_eq!(client.allowance(&alice, &bob), 0);
+    
+    assert_eq!(
+        client.try_mint(&admin, &alice, &1),
+        Err(Ok(TokenError::Unauthorized))
+    );
+    assert_eq!(client.migrate(), upgrade::CURRENT_SCHEMA_VERSION);
+    assert!(!client.clawback_enabled());
+    client.mint(&admin, &alice, &1);
+    assert_eq!(client.allowance(&alice, &bob), 300);
+    
+    // El primer gasto lo reescribe en temporary storage con el
+    // vencimiento que fijó migrate()
+    let expiration_ledger = env.ledger().sequence() + allowance::LEGACY_ALLOWANCE_LEDGERS;
+    client.transfer_from(&bob, &alice, &bob, &100);
+    assert_eq!(client.allowance(&alice, &bob), 200);
+    let key = DataKey::Allowance(alice.clone(), bob.clone());
+    let (value, legacy): (crate::storage::AllowanceValue, bool) = env.as_contract(&contract_id, || {
+        (env.storage().temporary().get(&key).unwrap(), env.storage().persistent().has(&key))
+    });
+    assert_eq!(value.amount, 200);
+    assert_eq!(value.expiration_ledger, expiration_ledger);
+    assert!(!legacy);
+}
+
+/// Test: upgrade y migrate requieren la firma del admin
+#[test]
+fn test_upgrade_requires_admin() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    // Sin mock de auths, ninguna firma está disponible
+    let new_wasm_hash = BytesN::from_array(&env, &[7u8; 32]);
+    assert!(client.try_upgrade(&new_wasm_hash).is_err());
+    assert!(client.try_migrate().is_err());
+    assert_eq!(client.schema_version(), 2);
+}
+
+/// Test: cualquiera puede renovar el TTL de un balance
+/// 
+/// Un balance que nadie toca se archivaría; extend_balance_ttl
+/// lo vuelve a llevar al TTL de la política vigente.
+#[test]
+fn test_extend_balance_ttl() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    
+    let balance_ttl = || env.as_contract(&contract_id, || {
+        env.storage().persistent().get_ttl(&DataKey::Balance(alice.clone()))
+    });
+    assert_eq!(balance_ttl(), 200_000);
+    
+    // Pasan 150_000 ledgers sin movimientos: queda bajo el umbral
+    env.ledger().set_sequence_number(150_000);
+    assert_eq!(balance_ttl(), 50_000);
+    
+    client.extend_balance_ttl(&alice);
+    assert_eq!(balance_ttl(), 200_000);
+    
+    // Sin balance no hay nada que extender
+    assert_eq!(client.try_extend_balance_ttl(&bob), Err(Ok(TokenError::EntryNotFound)));
+}
+
+/// Test: renovar allowance e instance, y configurar la política de TTL
+#[test]
+fn test_ttl_config_and_keepalive() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    
+    // Nueva política: balances viven 300_000 ledgers
+    let config = TtlConfig {
+        instance_threshold: 200_000,
+        instance_extend_to: 400_000,
+        persistent_threshold: 150_000,
+        persistent_extend_to: 300_000,
+    };
+    client.set_ttl_config(&config);
+    assert_eq!(client.ttl_config(), config);
+    
+    client.mint(&admin, &alice, &1000);
+    let balance_ttl = env.as_contract(&contract_id, || {
+        env.storage().persistent().get_ttl(&DataKey::Balance(alice.clone()))
+    });
+    assert_eq!(balance_ttl, 300_000);
+    
+    // Keepalive del contrato
+    client.extend_instance_ttl();
+    let instance_ttl = env.as_contract(&contract_id, || {
+        env.storage().instance().get_ttl()
+    });
+    assert_eq!(instance_ttl, 400_000);
+    
+    // El allowance se renueva solo hasta su expiración
+    client.approve(&alice, &bob, &100, &50_000);
+    client.extend_allowance_ttl(&alice, &bob);
+    let allowance_ttl = env.as_contract(&contract_id, || {
+        env.storage().temporary().get_ttl(&DataKey::Allowance(alice.clone(), bob.clone()))
+    });
+    assert_eq!(allowance_ttl, 50_000);
+    
+    // threshold > extend_to no es válido
+    let invalid = TtlConfig {
+        instance_threshold: 10,
+        instance_extend_to: 5,
+        persistent_threshold: 1,
+        persistent_extend_to: 2,
+    };
+    assert_eq!(client.try_set_ttl_config(&invalid), Err(Ok(TokenError::InvalidTtlConfig)));
+}
+
+/// Test de batch_transfer: payroll a varios destinatarios
+/// 
+/// Una sola autorización de Alice, un evento transfer por
+/// destinatario y un evento resumen con (cantidad, total).
+#[test]
+fn test_batch_transfer() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    
+    client.batch_transfer(&alice, &vec![&env, (bob.clone(), 300), (charlie.clone(), 200)]);
+    
+    // Una sola firma para todo el lote
+    assert_eq!(env.auths().len(), 1);
+    assert_eq!(env.auths()[0].0, alice);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("transfer"), alice.clone(), bob.clone()).into_val(&env),
+                300i128.into_val(&env)
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("transfer"), alice.clone(), charlie.clone()).into_val(&env),
+                200i128.into_val(&env)
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("batch_tx"), alice.clone(), EVENT_SCHEMA_VERSION).into_val(&env),
+                BatchTransferEvent {
+                    from: alice.clone(),
+                    count: 2,
+                    total: 500,
+                    version: EVENT_SCHEMA_VERSION,
+                }.data(&env)
+            )
+        ]
+    );
+    
+    assert_eq!(client.balance(&alice), 500);
+    assert_eq!(client.balance(&bob), 300);
+    assert_eq!(client.balance(&charlie), 200);
+}
+
+/// Test: batch_transfer es todo o nada
+/// 
+/// Si el total supera el balance o alguna entrada es inválida,
+/// ningún destinatario recibe nada.
+#[test]
+fn test_batch_transfer_all_or_nothing() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    
+    // El total (1100) supera el balance aunque cada monto por separado no
+    let result = client.try_batch_transfer(
+        &alice,
+        &vec![&env, (bob.clone(), 600), (charlie.clone(), 500)]
+    );
+    assert_eq!(result, Err(Ok(TokenError::InsufficientBalance)));
+    
+    // Un monto inválido en el medio rechaza todo el lote
+    let result = client.try_batch_transfer(
+        &alice,
+        &vec![&env, (bob.clone(), 100), (charlie.clone(), 0)]
+    );
+    assert_eq!(result, Err(Ok(TokenError::InvalidAmount)));
+    
+    assert_eq!(
+        client.try_batch_transfer(&alice, &Vec::new(&env)),
+        Err(Ok(TokenError::EmptyBatch))
+    );
+    
+    assert_eq!(client.balance(&alice), 1000);
+    assert_eq!(client.balance(&bob), 0);
+    assert_eq!(client.balance(&charlie), 0);
+}
+
+/// Límites por transacción de mainnet que debe respetar un lote
+const TX_MAX_WRITE_ENTRIES: u32 = 50;
+const TX_MAX_INSTRUCTIONS: i64 = 100_000_000;
+
+/// Costo de batch_transfer contra los límites de la red
+/// 
+/// Peor caso: MAX_BATCH destinatarios que aún no tienen BDB, fee
+/// activo con una treasury nueva y el primer movimiento del remitente
+/// en el ledger. Tiene que entrar en una transacción de mainnet; un
+/// destinatario más falla con BatchTooLarge. Lo mismo para batch_mint.
+/// Correr con `cargo test batch_transfer_budget -- --nocapture`
+/// para ver las cifras
+#[test]
+fn test_batch_transfer_budget() {
+    extern crate std;
+    
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1_000_000);
+    client.set_transfer_fee(&100, &Address::generate(&env));
+    
+    let mut recipients = Vec::new(&env);
+    for _ in 0..MAX_BATCH {
+        recipients.push_back((Address::generate(&env), 1000i128));
+    }
+    env.ledger().set_sequence_number(env.ledger().sequence() + 1);
+    
+    env.cost_estimate().budget().reset_unlimited();
+    client.batch_transfer(&alice, &recipients);
+    let resources = env.cost_estimate().resources();
+    
+    std::println!(
+        "batch_transfer {} destinatarios: cpu = {} instr, mem = {} bytes, escrituras = {}",
+        MAX_BATCH,
+        resources.instructions,
+        resources.mem_bytes,
+        resources.write_entries
+    );
+    assert!(resources.write_entries <= TX_MAX_WRITE_ENTRIES);
+    assert!(resources.instructions <= TX_MAX_INSTRUCTIONS);
+    assert_eq!(client.balance(&alice), 1_000_000 - 1000 * MAX_BATCH as i128);
+    
+    // Un destinatario más no entra
+    recipients.push_back((Address::generate(&env), 1000i128));
+    assert_eq!(
+        client.try_batch_transfer(&alice, &recipients),
+        Err(Ok(TokenError::BatchTooLarge))
+    );
+    
+    // batch_mint con MAX_BATCH destinatarios nuevos también entra
+    let mut recipients = Vec::new(&env);
+    for _ in 0..MAX_BATCH {
+        recipients.push_back((Address::generate(&env), 1000i128));
+    }
+    env.ledger().set_sequence_number(env.ledger().sequence() + 1);
+    
+    env.cost_estimate().budget().reset_unlimited();
+    client.batch_mint(&admin, &recipients);
+    let resources = env.cost_estimate().resources();
+    
+    std::println!(
+        "batch_mint {} destinatarios: cpu = {} instr, mem = {} bytes, escrituras = {}",
+        MAX_BATCH,
+        resources.instructions,
+        resources.mem_bytes,
+        resources.write_entries
+    );
+    assert!(resources.write_entries <= TX_MAX_WRITE_ENTRIES);
+    assert!(resources.instructions <= TX_MAX_INSTRUCTIONS);
+}
+
+/// Test de batch_mint: distribución inicial
+/// 
+/// Una sola firma del minter, un evento mint por destinatario,
+/// un evento resumen y el supply total actualizado una vez.
+#[test]
+fn test_batch_mint() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    client.batch_mint(&admin, &vec![&env, (alice.clone(), 700), (bob.clone(), 300)]);
+    
+    assert_eq!(env.auths().len(), 1);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("mint"), alice.clone()).into_val(&env),
+                700i128.into_val(&env)
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("mint"), bob.clone()).into_val(&env),
+                300i128.into_val(&env)
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("batch_mnt"), admin.clone(), EVENT_SCHEMA_VERSION).into_val(&env),
+                BatchMintEvent {
+                    minter: admin.clone(),
+                    count: 2,
+                    total: 1000,
+                    remaining_quota: None,
+                    version: EVENT_SCHEMA_VERSION,
+                }.data(&env)
+            )
+        ]
+    );
+    
+    assert_eq!(client.balance(&alice), 700);
+    assert_eq!(client.balance(&bob), 300);
+    assert_eq!(client.total_supply(), 1000);
+}
+
+/// Test: batch_mint respeta max_supply y la pausa, y es todo o nada
+#[test]
+fn test_batch_mint_cap_pause_and_invalid_amount() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &Some(1000),
+        &false
+    );
+    
+    env.mock_all_auths();
+    
+    // El total del lote (1100) supera el tope
+    assert_eq!(
+        client.try_batch_mint(&admin, &vec![&env, (alice.clone(), 600), (bob.clone(), 500)]),
+        Err(Ok(TokenError::SupplyCapExceeded))
+    );
+    
+    // Un monto inválido en el índice 1 rechaza todo el lote
+    assert_eq!(
+        client.try_batch_mint(&admin, &vec![&env, (alice.clone(), 100), (bob.clone(), -5)]),
+        Err(Ok(TokenError::InvalidAmount))
+    );
+    
+    assert_eq!(
+        client.try_batch_mint(&admin, &Vec::new(&env)),
+        Err(Ok(TokenError::EmptyBatch))
+    );
+    
+    // Con el contrato pausado no se mintea
+    client.pause(&admin);
+    assert_eq!(
+        client.try_batch_mint(&admin, &vec![&env, (alice.clone(), 100)]),
+        Err(Ok(TokenError::ContractPaused))
+    );
+    
+    assert_eq!(client.balance(&alice), 0);
+    assert_eq!(client.balance(&bob), 0);
+    assert_eq!(client.total_supply(), 0);
+}
+
+/// Test: first_invalid_mint_entry ubica la entrada que rechaza el lote
+/// 
+/// Misma validación que batch_mint, sin escribir nada: monto no
+/// positivo, menos de una share o destinatario congelado
+#[test]
+fn test_batch_mint_reports_invalid_index() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    client.set_authorized(&admin, &carol, &false);
+    
+    // Destinatario congelado en el índice 2
+    let batch = vec![&env, (alice.clone(), 100), (bob.clone(), 50), (carol.clone(), 10)];
+    assert_eq!(client.first_invalid_mint_entry(&batch), Some(2));
+    assert_eq!(
+        client.try_batch_mint(&admin, &batch),
+        Err(Ok(TokenError::AccountNotAuthorized))
+    );
+    
+    // Monto inválido en el índice 1 (antes que el congelado)
+    let batch = vec![&env, (alice.clone(), 100), (bob.clone(), 0), (carol.clone(), 10)];
+    assert_eq!(client.first_invalid_mint_entry(&batch), Some(1));
+    assert_eq!(
+        client.try_batch_mint(&admin, &batch),
+        Err(Ok(TokenError::InvalidAmount))
+    );
+    
+    // Lote válido
+    let batch = vec![&env, (alice.clone(), 100), (bob.clone(), 50)];
+    assert_eq!(client.first_invalid_mint_entry(&batch), None);
+    client.batch_mint(&admin, &batch);
+    assert_eq!(client.total_supply(), 150);
+    
+    // Más de MAX_BATCH destinatarios
+    let mut batch = Vec::new(&env);
+    for _ in 0..=MAX_BATCH {
+        batch.push_back((Address::generate(&env), 1i128));
+    }
+    assert_eq!(client.try_batch_mint(&admin, &batch), Err(Ok(TokenError::BatchTooLarge)));
+}
+
+/// Test: batch_mint exige el rol Minter y consume la cuota del minter
+#[test]
+fn test_batch_mint_requires_minter_and_consumes_quota() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let minter = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    
+    // Sin rol Minter no se mintea en lote
+    assert_eq!(
+        client.try_batch_mint(&minter, &vec![&env, (alice.clone(), 100)]),
+        Err(Ok(TokenError::Unauthorized))
+    );
+    
+    client.grant_role(&admin, &Role::Minter, &minter);
+    client.set_minter_quota(&admin, &minter, &500, &1_000);
+    
+    // El lote consume la cuota por el total
+    client.batch_mint(&minter, &vec![&env, (alice.clone(), 300), (bob.clone(), 100)]);
+    assert_eq!(env.auths()[0].0, minter);
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(
+        TokenEvent::decode(&env, &topics, &data),
+        Ok(TokenEvent::BatchMint(BatchMintEvent {
+            minter: minter.clone(),
+            count: 2,
+            total: 400,
+            remaining_quota: Some(100),
+            version: EVENT_SCHEMA_VERSION,
+        }))
+    );
+    
+    // El siguiente lote pasa la cuota y se revierte completo
+    assert_eq!(
+        client.try_batch_mint(&minter, &vec![&env, (alice.clone(), 50), (bob.clone(), 51)]),
+        Err(Ok(TokenError::MinterQuotaExceeded))
+    );
+    assert_eq!(client.total_supply(), 400);
+}
+
+/// Test de balance_at / total_supply_at
+/// 
+/// Cada cambio de balance deja un checkpoint y las consultas
+/// devuelven el valor vigente al cierre del ledger pedido.
+#[test]
+fn test_balance_at_checkpoints() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    
+    env.ledger().set_sequence_number(10);
+    client.mint(&admin, &alice, &1000);
+    
+    // Dos transfers en el mismo ledger: queda el valor final
+    env.ledger().set_sequence_number(20);
+    client.transfer(&alice, &bob, &200);
+    client.approve(&alice, &bob, &100, &1000);
+    client.transfer_from(&bob, &alice, &bob, &100);
+    
+    env.ledger().set_sequence_number(30);
+    client.burn(&alice, &100);
+    
+    env.ledger().set_sequence_number(40);
+    
+    assert_eq!(client.balance_at(&alice, &5), 0);
+    assert_eq!(client.balance_at(&alice, &10), 1000);
+    assert_eq!(client.balance_at(&alice, &19), 1000);
+    assert_eq!(client.balance_at(&alice, &20), 700);
+    assert_eq!(client.balance_at(&alice, &30), 600);
+    assert_eq!(client.balance_at(&alice, &39), 600);
+    assert_eq!(client.balance_at(&bob, &19), 0);
+    assert_eq!(client.balance_at(&bob, &25), 300);
+    
+    assert_eq!(client.total_supply_at(&9), 0);
+    assert_eq!(client.total_supply_at(&10), 1000);
+    assert_eq!(client.total_supply_at(&30), 900);
+    
+    // Una cuenta sin movimientos vale su balance actual (0)
+    assert_eq!(client.balance_at(&Address::generate(&env), &15), 0);
+    
+    // El ledger actual todavía no cerró: también es futuro
+    assert_eq!(client.try_balance_at(&alice, &40), Err(Ok(TokenError::FutureLedger)));
+    assert_eq!(client.try_total_supply_at(&40), Err(Ok(TokenError::FutureLedger)));
+}
+
+/// Test: poda del historial con el horizonte de checkpoints
+/// 
+/// Después de subir el horizonte las consultas anteriores fallan,
+/// las posteriores siguen dando el mismo resultado y el horizonte
+/// no puede bajar.
+#[test]
+fn test_checkpoint_pruning() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    
+    // Checkpoints de alice en los ledgers 10, 20, 30 y 40
+    for ledger in [10u32, 20, 30, 40] {
+        env.ledger().set_sequence_number(ledger);
+        client.mint(&admin, &alice, &100);
+    }
+    env.ledger().set_sequence_number(50);
+    
+    client.set_checkpoint_horizon(&25);
+    assert_eq!(client.checkpoint_horizon(), 25);
+    
+    // Se conserva el checkpoint del ledger 20 (vigente en el horizonte)
+    assert_eq!(client.prune_checkpoints(&alice), 1);
+    assert_eq!(client.prune_checkpoints(&alice), 0);
+    
+    assert_eq!(client.try_balance_at(&alice, &24), Err(Ok(TokenError::HistoryPruned)));
+    assert_eq!(client.balance_at(&alice, &25), 200);
+    assert_eq!(client.balance_at(&alice, &35), 300);
+    assert_eq!(client.total_supply_at(&25), 200);
+    assert_eq!(client.total_supply_at(&49), 400);
+    
+    // El horizonte no baja ni pasa el ledger actual
+    assert_eq!(
+        client.try_set_checkpoint_horizon(&20),
+        Err(Ok(TokenError::InvalidCheckpointHorizon))
+    );
+    assert_eq!(
+        client.try_set_checkpoint_horizon(&51),
+        Err(Ok(TokenError::InvalidCheckpointHorizon))
+    );
+}
+
+/// Test: un checkpoint por entrada de storage y poda acotada
+/// 
+/// El historial no vive en una sola entrada (que chocaría con el tope
+/// de tamaño): cada checkpoint es su propia key. La poda explícita borra
+/// como máximo MAX_PRUNE_PER_CALL entradas por llamada.
+#[test]
+fn test_checkpoints_stored_per_entry() {
+    use crate::storage::{Checkpoint, CheckpointRange, CheckpointSeries};
+    
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    for ledger in 1..=150u32 {
+        env.ledger().set_sequence_number(ledger);
+        client.mint(&admin, &alice, &1);
+    }
+    env.ledger().set_sequence_number(200);
+    
+    let series = CheckpointSeries::Balance(alice.clone());
+    env.as_contract(&contract_id, || {
+        let range: CheckpointRange = env.storage().persistent()
+            .get(&DataKey::Checkpoints(series.clone()))
+            .unwrap();
+        assert_eq!(range, CheckpointRange { start: 0, end: 150 });
+        let checkpoint: Checkpoint = env.storage().persistent()
+            .get(&DataKey::CheckpointAt(series.clone(), 74))
+            .unwrap();
+        assert_eq!(checkpoint, Checkpoint { ledger: 75, value: 75 });
+    });
+    assert_eq!(client.balance_at(&alice, &75), 75);
+    assert_eq!(client.balance_at(&alice, &199), 150);
+    
+    // 149 checkpoints anteriores al horizonte: dos llamadas
+    client.set_checkpoint_horizon(&200);
+    assert_eq!(client.prune_checkpoints(&alice), checkpoints::MAX_PRUNE_PER_CALL);
+    assert_eq!(client.prune_checkpoints(&alice), 149 - checkpoints::MAX_PRUNE_PER_CALL);
+    assert_eq!(client.prune_checkpoints(&alice), 0);
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().persistent().has(&DataKey::CheckpointAt(series.clone(), 148)));
+        assert!(env.storage().persistent().has(&DataKey::CheckpointAt(series.clone(), 149)));
+    });
+    
+    env.ledger().set_sequence_number(201);
+    assert_eq!(client.balance_at(&alice, &200), 150);
+}
+
+/// Test de delegación de votos
+/// 
+/// Los votos aparecen al delegar, siguen a transfer / mint / burn y
+/// se mueven completos al cambiar de delegado. get_past_votes lee
+/// el historial por ledger.
+#[test]
+fn test_delegation_follows_balances() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    
+    // Sin delegar, el balance no cuenta como votos
+    env.ledger().set_sequence_number(10);
+    client.mint(&admin, &alice, &1000);
+    assert_eq!(client.get_votes(&alice), 0);
+    
+    // Alice vota con su propio balance
+    env.ledger().set_sequence_number(11);
+    client.delegate(&alice, &alice);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("votes_chg"), alice.clone(), EVENT_SCHEMA_VERSION).into_val(&env),
+                VotesChangedEvent {
+                    delegatee: alice.clone(),
+                    previous: 0,
+                    new_votes: 1000,
+                    version: EVENT_SCHEMA_VERSION,
+                }.data(&env)
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("delgt_chg"), alice.clone(), EVENT_SCHEMA_VERSION).into_val(&env),
+                DelegateChangedEvent {
+                    delegator: alice.clone(),
+                    previous: None,
+                    delegatee: alice.clone(),
+                    version: EVENT_SCHEMA_VERSION,
+                }.data(&env)
+            )
+        ]
+    );
+    assert_eq!(client.delegates(&alice), Some(alice.clone()));
+    
+    // Los votos siguen al transfer
+    client.delegate(&bob, &carol);
+    env.ledger().set_sequence_number(12);
+    client.transfer(&alice, &bob, &300);
+    assert_eq!(client.get_votes(&alice), 700);
+    assert_eq!(client.get_votes(&carol), 300);
+    
+    // Cambiar de delegado mueve todo el balance
+    env.ledger().set_sequence_number(13);
+    client.delegate(&alice, &carol);
+    assert_eq!(client.get_votes(&alice), 0);
+    assert_eq!(client.get_votes(&carol), 1000);
+    
+    // ...y también burn y mint
+    env.ledger().set_sequence_number(14);
+    client.burn(&bob, &100);
+    env.ledger().set_sequence_number(15);
+    client.mint(&admin, &alice, &50);
+    assert_eq!(client.get_votes(&carol), 950);
+    
+    env.ledger().set_sequence_number(16);
+    assert_eq!(client.get_past_votes(&alice, &10), 0);
+    assert_eq!(client.get_past_votes(&alice, &11), 1000);
+    assert_eq!(client.get_past_votes(&alice, &12), 700);
+    assert_eq!(client.get_past_votes(&alice, &13), 0);
+    assert_eq!(client.get_past_votes(&carol, &12), 300);
+    assert_eq!(client.get_past_votes(&carol, &14), 900);
+    assert_eq!(client.get_past_votes(&carol, &15), 950);
+    
+    // Los votos del ledger actual todavía se pueden mover (p. ej.
+    // recibir tokens y votar en la misma transacción)
+    assert_eq!(
+        client.try_get_past_votes(&carol, &16),
+        Err(Ok(TokenError::FutureLedger))
+    );
+}
+
+/// Dirección de cuenta (G...) de una clave ed25519
+/// 
+/// Mismo layout XDR que valida permit::owner_matches_key
+fn account_address(env: &Env, key: &ed25519_dalek::SigningKey) -> Address {
+    use soroban_sdk::{xdr::FromXdr, Bytes};
+    
+    let mut xdr = Bytes::from_array(env, &[0, 0, 0, 18, 0, 0, 0, 0, 0, 0, 0, 0]);
+    xdr.extend_from_array(&key.verifying_key().to_bytes());
+    Address::from_xdr(env, &xdr).unwrap()
+}
+
+/// Firma off-chain de un permit, como lo haría la wallet del owner
+fn sign_permit(
+    env: &Env,
+    contract_id: &Address,
+    key: &ed25519_dalek::SigningKey,
+    owner: &Address,
+    spender: &Address,
+    amount: i128,
+    expiration_ledger: u32,
+    nonce: u64,
+    deadline_ledger: u32
+) -> BytesN<64> {
+    extern crate std;
+    use crate::storage::PermitPayload;
+    use ed25519_dalek::Signer;
+    use soroban_sdk::xdr::ToXdr;
+    
+    let payload = PermitPayload {
+        contract: contract_id.clone(),
+        network_id: env.ledger().network_id(),
+        owner: owner.clone(),
+        spender: spender.clone(),
+        amount,
+        expiration_ledger,
+        nonce,
+        deadline_ledger,
+    }
+    .to_xdr(env);
+    let message: std::vec::Vec<u8> = payload.iter().collect();
+    BytesN::from_array(env, &key.sign(&message).to_bytes())
+}
+
+/// Test de permit: approve con firma off-chain
+/// 
+/// Cualquiera envía el permit (sin firma de transacción del owner),
+/// el allowance queda escrito, el nonce avanza y la misma firma
+/// no se puede volver a usar.
+#[test]
+fn test_permit() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+    let owner = account_address(&env, &key);
+    let public_key = BytesN::from_array(&env, &key.verifying_key().to_bytes());
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    assert_eq!(client.nonce(&owner), 0);
+    let signature = sign_permit(&env, &contract_id, &key, &owner, &spender, 500, 1000, 0, 100);
+    
+    // Sin mock de auths: la firma del mensaje es la única autorización
+    client.permit(&owner, &spender, &500, &1000, &0, &100, &public_key, &signature);
+    
+    assert!(env.auths().is_empty());
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("approve"), owner.clone(), spender.clone()).into_val(&env),
+                (500i128, 1000u32).into_val(&env)
+            )
+        ]
+    );
+    assert_eq!(client.allowance(&owner, &spender), 500);
+    assert_eq!(client.nonce(&owner), 1);
+    
+    // Replay de la misma firma
+    assert_eq!(
+        client.try_permit(&owner, &spender, &500, &1000, &0, &100, &public_key, &signature),
+        Err(Ok(TokenError::InvalidNonce))
+    );
+}
+
+/// Test: permit rechaza firmas que no corresponden
+/// 
+/// - Clave que no es la del owner
+/// - Mensaje alterado (otro amount u otro deadline) o firmado para
+///   otro contrato
+/// - Firma vencida (deadline_ledger superado)
+#[test]
+fn test_permit_rejects_invalid_signatures() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let spender = Address::generate(&env);
+    let key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+    let owner = account_address(&env, &key);
+    let public_key = BytesN::from_array(&env, &key.verifying_key().to_bytes());
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    // Un tercero firma con su clave un permit sobre los tokens del owner
+    let attacker = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]);
+    let signature = sign_permit(&env, &contract_id, &attacker, &owner, &spender, 500, 1000, 0, 100);
+    assert_eq!(
+        client.try_permit(
+            &owner,
+            &spender,
+            &500,
+            &1000,
+            &0,
+            &100,
+            &BytesN::from_array(&env, &attacker.verifying_key().to_bytes()),
+            &signature
+        ),
+        Err(Ok(TokenError::Unauthorized))
+    );
+    
+    // Firma válida para 500 presentada con otro amount o más plazo
+    let signature = sign_permit(&env, &contract_id, &key, &owner, &spender, 500, 1000, 0, 100);
+    assert!(client.try_permit(&owner, &spender, &5000, &1000, &0, &100, &public_key, &signature).is_err());
+    assert!(client.try_permit(&owner, &spender, &500, &1000, &0, &200, &public_key, &signature).is_err());
+    
+    // Firma para otro contrato (dominio distinto)
+    let other_contract = Address::generate(&env);
+    let other = sign_permit(&env, &other_contract, &key, &owner, &spender, 500, 1000, 0, 100);
+    assert!(client.try_permit(&owner, &spender, &500, &1000, &0, &100, &public_key, &other).is_err());
+    
+    // Pasado el deadline la firma correcta ya no sirve
+    env.ledger().with_mut(|li| li.sequence_number = 101);
+    assert_eq!(
+        client.try_permit(&owner, &spender, &500, &1000, &0, &100, &public_key, &signature),
+        Err(Ok(TokenError::PermitExpired))
+    );
+    
+    assert_eq!(client.allowance(&owner, &spender), 0);
+    assert_eq!(client.nonce(&owner), 0);
+}
+
+/// Test de fee de transferencia
+/// 
+/// transfer y transfer_from cobran el fee: `to` recibe el neto,
+/// la treasury el fee, y los eventos reportan gross / fee / net.
+#[test]
+fn test_transfer_fee() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &10_000);
+    
+    // 2.5%
+    client.set_transfer_fee(&250, &treasury);
+    assert_eq!(client.transfer_fee(), Some(FeeConfig { fee_bps: 250, treasury: treasury.clone() }));
+    
+    client.transfer(&alice, &bob, &1000);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("transfer"), alice.clone(), bob.clone()).into_val(&env),
+                975i128.into_val(&env)
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("transfer"), alice.clone(), treasury.clone()).into_val(&env),
+                25i128.into_val(&env)
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("xfer_fee"), alice.clone(), bob.clone(), EVENT_SCHEMA_VERSION).into_val(&env),
+                TransferFeeEvent {
+                    from: alice.clone(),
+                    to: bob.clone(),
+                    gross: 1000,
+                    fee: 25,
+                    net: 975,
+                    version: EVENT_SCHEMA_VERSION,
+                }.data(&env)
+            )
+        ]
+    );
+    assert_eq!(client.balance(&alice), 9000);
+    assert_eq!(client.balance(&bob), 975);
+    assert_eq!(client.balance(&treasury), 25);
+    
+    // transfer_from consume el allowance por el monto bruto
+    client.approve(&alice, &bob, &400, &1000);
+    client.transfer_from(&bob, &alice, &bob, &400);
+    assert_eq!(client.allowance(&alice, &bob), 0);
+    assert_eq!(client.balance(&bob), 975 + 390);
+    assert_eq!(client.balance(&treasury), 35);
+    
+    // Montos chicos: el fee redondea a 0
+    client.transfer(&alice, &bob, &39);
+    assert_eq!(client.balance(&bob), 975 + 390 + 39);
+    
+    // Con la treasury congelada no se puede cobrar el fee
+    client.set_authorized(&admin, &treasury, &false);
+    assert_eq!(
+        client.try_transfer(&alice, &bob, &1000),
+        Err(Ok(TokenError::AccountNotAuthorized.into()))
+    );
+    client.set_authorized(&admin, &treasury, &true);
+    
+    // El supply no cambia: el fee es una transferencia
+    assert_eq!(client.total_supply(), 10_000);
+}
+
+/// Test: exentos, techo de fee y desactivación
+#[test]
+fn test_transfer_fee_exemptions_and_ceiling() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let pool = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &10_000);
+    
+    // Ni el admin puede pasar el techo
+    assert_eq!(
+        client.try_set_transfer_fee(&(MAX_FEE_BPS + 1), &treasury),
+        Err(Ok(TokenError::FeeTooHigh))
+    );
+    client.set_transfer_fee(&MAX_FEE_BPS, &treasury);
+    
+    // El pool exento no paga fee en ninguna dirección
+    client.set_fee_exempt(&pool, &true);
+    assert!(client.is_fee_exempt(&pool));
+    client.transfer(&alice, &pool, &1000);
+    assert_eq!(client.balance(&pool), 1000);
+    client.transfer(&pool, &alice, &500);
+    assert_eq!(client.balance(&alice), 9500);
+    
+    // La treasury tampoco
+    client.transfer(&alice, &treasury, &100);
+    assert_eq!(client.balance(&treasury), 100);
+    
+    // Sin exención se cobra el 10%
+    client.set_fee_exempt(&pool, &false);
+    client.transfer(&alice, &pool, &1000);
+    assert_eq!(client.balance(&pool), 500 + 900);
+    assert_eq!(client.balance(&treasury), 200);
+    
+    // fee_bps = 0 desactiva el fee
+    client.set_transfer_fee(&0, &treasury);
+    assert_eq!(client.transfer_fee(), None);
+    client.transfer(&alice, &pool, &1000);
+    assert_eq!(client.balance(&pool), 1400 + 1000);
+}
+
+/// Receptor de prueba para transfer_and_call
+/// 
+/// Guarda el último depósito recibido; rechaza si `data` es "reject"
+mod receiver {
+    use soroban_sdk::{
+        contract, contracterror, contractimpl, Address, Bytes, Env, Symbol
+    };
+    
+    #[contracterror]
+    #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+    #[repr(u32)]
+    pub enum ReceiverError {
+        Rejected = 1,
+    }
+    
+    #[contract]
+    pub struct MockReceiver;
+    
+    #[contractimpl]
+    impl MockReceiver {
+        pub fn on_token_received(
+            env: Env,
+            token: Address,
+            from: Address,
+            amount: i128,
+            data: Bytes
+        ) -> Result<(), ReceiverError> {
+            if data == Bytes::from_slice(&env, b"reject") {
+                return Err(ReceiverError::Rejected);
+            }
+            env.storage().instance().set(
+                &Symbol::new(&env, "last_deposit"),
+                &(token, from, amount, data)
+            );
+            Ok(())
+        }
+        
+        pub fn last_deposit(env: Env) -> Option<(Address, Address, i128, Bytes)> {
+            env.storage().instance().get(&Symbol::new(&env, "last_deposit"))
+        }
+    }
+}
+
+/// Test de transfer_and_call: depósito en un vault en una sola transacción
+/// 
+/// El receptor recibe los tokens y el hook con (token, from, amount, data).
+#[test]
+fn test_transfer_and_call() {
+    use soroban_sdk::Bytes;
+    
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    let vault_id = env.register(receiver::MockReceiver, ());
+    let vault = receiver::MockReceiverClient::new(&env, &vault_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    
+    let data = Bytes::from_slice(&env, b"deposit:42");
+    client.transfer_and_call(&alice, &vault_id, &300, &data);
+    
+    assert_eq!(client.balance(&alice), 700);
+    assert_eq!(client.balance(&vault_id), 300);
+    assert_eq!(
+        vault.last_deposit(),
+        Some((contract_id.clone(), alice.clone(), 300, data))
+    );
+}
+
+/// Test: si el receptor rechaza o no tiene hook se revierte todo
+#[test]
+fn test_transfer_and_call_rolls_back() {
+    use soroban_sdk::Bytes;
+    
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    let vault_id = env.register(receiver::MockReceiver, ());
+    
+    // Un contrato cualquiera sin on_token_received
+    let no_hook_id = env.register(TokenBDB, ());
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    client.mint(&admin, &alice, &1000);
+    
+    assert_eq!(
+        client.try_transfer_and_call(&alice, &vault_id, &300, &Bytes::from_slice(&env, b"reject")),
+        Err(Ok(TokenError::ReceiverRejected))
+    );
+    assert_eq!(
+        client.try_transfer_and_call(&alice, &no_hook_id, &300, &Bytes::new(&env)),
+        Err(Ok(TokenError::ReceiverRejected))
+    );
+    
+    assert_eq!(client.balance(&alice), 1000);
+    assert_eq!(client.balance(&vault_id), 0);
+    assert_eq!(client.balance(&no_hook_id), 0);
+}
+
+/// Test: modo rebasing, los balances siguen al índice
+/// 
+/// Mint / transfer / burn convierten a shares redondeando hacia
+/// abajo, y balance_at usa el índice vigente en cada ledger
+#[test]
+fn test_rebasing_mode() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &true
+    );
+    
+    env.mock_all_auths();
+    assert!(client.is_rebasing());
+    
+    // Invariante: las shares de las cuentas suman exactamente el total
+    let accounts = [alice.clone(), bob.clone(), carol.clone(), treasury.clone()];
+    let assert_shares_conserved = || {
+        let sum: i128 = accounts.iter().map(|account| client.shares_of(account)).sum();
+        assert_eq!(sum, client.total_shares());
+    };
+    assert_eq!(client.rebase_index(), 1_000_000_000);
+    
+    // Índice 1.0: 1 share = 1 token
+    env.ledger().set_sequence_number(10);
+    client.mint(&admin, &alice, &1000);
+    assert_eq!(client.shares_of(&alice), 1000);
+    assert_shares_conserved();
+    
+    // Índice 1.5: 50% de rendimiento sin tocar los balances
+    env.ledger().set_sequence_number(20);
+    client.rebase(&admin, &1_500_000_000);
+    assert_eq!(client.balance(&alice), 1500);
+    assert_eq!(client.total_supply(), 1500);
+    assert_eq!(client.shares_of(&alice), 1000);
+    
+    // 300 tokens = 200 shares
+    env.ledger().set_sequence_number(30);
+    client.transfer(&alice, &bob, &300);
+    assert_eq!(client.shares_of(&bob), 200);
+    assert_eq!(client.balance(&alice), 1200);
+    assert_eq!(client.balance(&bob), 300);
+    assert_shares_conserved();
+    
+    // 100 tokens = 66.6 shares -> 66 (bob recibe 99)
+    client.transfer(&alice, &bob, &100);
+    assert_eq!(client.shares_of(&alice), 734);
+    assert_eq!(client.balance(&bob), 399);
+    assert_shares_conserved();
+    
+    // Menos de una share no se puede mover
+    assert_eq!(
+        client.try_transfer(&alice, &bob, &1),
+        Err(Ok(TokenError::InvalidAmount.into()))
+    );
+    
+    // Mint y burn también pasan por shares
+    client.mint(&admin, &bob, &150);
+    assert_shares_conserved();
+    client.burn(&alice, &150);
+    assert_shares_conserved();
+    assert_eq!(client.shares_of(&bob), 366);
+    assert_eq!(client.shares_of(&alice), 634);
+    assert_eq!(client.total_shares(), 1000);
+    assert_eq!(client.total_supply(), 1500);
+    
+    // Historial con el índice de cada ledger
+    env.ledger().set_sequence_number(40);
+    assert_eq!(client.balance_at(&alice, &10), 1000);
+    assert_eq!(client.balance_at(&alice, &20), 1500);
+    assert_eq!(client.balance_at(&alice, &30), 951);
+    assert_eq!(client.total_supply_at(&10), 1000);
+    assert_eq!(client.total_supply_at(&20), 1500);
+    
+    // Con fee del 10%: el fee sale de las shares debitadas, no de
+    // convertir neto y fee por separado. 11 tokens = 7 shares y el
+    // 10% de 7 shares redondea a 0: bob recibe las 7
+    client.set_transfer_fee(&1000, &treasury);
+    client.transfer(&alice, &bob, &11);
+    assert_eq!(client.shares_of(&alice), 627);
+    assert_eq!(client.shares_of(&bob), 373);
+    assert_eq!(client.shares_of(&treasury), 0);
+    assert_shares_conserved();
+    
+    // 150 tokens = 100 shares: 10 a la treasury (15 tokens), 90 a bob
+    client.transfer(&alice, &bob, &150);
+    assert_eq!(client.shares_of(&alice), 527);
+    assert_eq!(client.shares_of(&bob), 463);
+    assert_eq!(client.shares_of(&treasury), 10);
+    assert_eq!(client.balance(&treasury), 15);
+    assert_shares_conserved();
+    
+    // Lotes: una entrada de menos de una share rechaza el lote
+    assert_eq!(
+        client.try_batch_transfer(&alice, &vec![&env, (bob.clone(), 1), (carol.clone(), 1)]),
+        Err(Ok(TokenError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_batch_mint(&admin, &vec![&env, (carol.clone(), 3), (bob.clone(), 1)]),
+        Err(Ok(TokenError::InvalidAmount))
+    );
+    
+    // El lote debita la suma de las shares de cada entrada:
+    // bob 20 shares (2 de fee), carol 2 shares (fee 0)
+    client.batch_transfer(&alice, &vec![&env, (bob.clone(), 30), (carol.clone(), 3)]);
+    assert_eq!(client.shares_of(&alice), 505);
+    assert_eq!(client.shares_of(&bob), 481);
+    assert_eq!(client.shares_of(&carol), 2);
+    assert_eq!(client.shares_of(&treasury), 12);
+    assert_shares_conserved();
+    
+    // batch_mint suma al total las shares que acredita (2 + 2)
+    client.batch_mint(&admin, &vec![&env, (carol.clone(), 3), (treasury.clone(), 4)]);
+    assert_eq!(client.shares_of(&carol), 4);
+    assert_eq!(client.shares_of(&treasury), 14);
+    assert_eq!(client.total_shares(), 1004);
+    assert_shares_conserved();
+}
+
+/// Test: rebase solo en tokens rebasing, por admin u Oracle
+#[test]
+fn test_rebase_validations() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let name = String::from_str(&env, "Token");
+    let symbol = String::from_str(&env, "TOK");
+    
+    // Token estándar: sin rebase y shares = balance
+    let standard = TokenBDBClient::new(&env, &env.register(TokenBDB, ()));
+    standard.initialize(&admin, &name, &symbol, &7, &false, &None, &false);
+    standard.mint(&admin, &oracle, &1000);
+    assert!(!standard.is_rebasing());
+    assert_eq!(standard.shares_of(&oracle), standard.balance(&oracle));
+    assert_eq!(
+        standard.try_rebase(&admin, &2_000_000_000),
+        Err(Ok(TokenError::NotRebasing))
+    );
+    
+    let client = TokenBDBClient::new(&env, &env.register(TokenBDB, ()));
+    client.initialize(&admin, &name, &symbol, &7, &false, &None, &true);
+    client.mint(&admin, &oracle, &1000);
+    
+    // Sin rol Oracle
+    assert_eq!(
+        client.try_rebase(&oracle, &1_100_000_000),
+        Err(Ok(TokenError::Unauthorized))
+    );
+    
+    // Con rol Oracle; el índice puede bajar pero no llegar a 0
+    client.grant_role(&admin, &Role::Oracle, &oracle);
+    client.rebase(&oracle, &900_000_000);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                client.address.clone(),
+                (symbol_short!("rebase"), oracle.clone(), EVENT_SCHEMA_VERSION).into_val(&env),
+                RebaseEvent {
+                    caller: oracle.clone(),
+                    old_index: 1_000_000_000,
+                    new_index: 900_000_000,
+                    version: EVENT_SCHEMA_VERSION,
+                }.data(&env)
+            )
+        ]
+    );
+    assert_eq!(client.balance(&oracle), 900);
+    assert_eq!(
+        client.try_rebase(&oracle, &0),
+        Err(Ok(TokenError::InvalidRebaseIndex))
+    );
+}
+
+/// Test: set_metadata actualiza name, symbol y los campos opcionales
+#[test]
+fn test_set_metadata() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let manager = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Builder Token"),
+        &String::from_str(&env, "BDB"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    
+    // Recién creado: sin campos opcionales
+    let initial = client.metadata();
+    assert_eq!(initial.name, String::from_str(&env, "Builder Token"));
+    assert_eq!(initial.decimals, 7);
+    assert_eq!(initial.description, None);
+    assert_eq!(initial.home_domain, None);
+    
+    client.grant_role(&admin, &Role::MetadataManager, &manager);
+    client.set_metadata(
+        &manager,
+        &String::from_str(&env, "Buen Dia Builders"),
+        &String::from_str(&env, "BDB2"),
+        &Some(String::from_str(&env, "Token de la comunidad")),
+        &Some(String::from_str(&env, "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi")),
+        &Some(String::from_str(&env, "buendiabuilders.com")),
+        &None
+    );
+    
+    let expected = TokenMetadata {
+        name: String::from_str(&env, "Buen Dia Builders"),
+        symbol: String::from_str(&env, "BDB2"),
+        decimals: 7,
+        description: Some(String::from_str(&env, "Token de la comunidad")),
+        image_uri: Some(String::from_str(
+            &env,
+            "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"
+        )),
+        home_domain: Some(String::from_str(&env, "buendiabuilders.com")),
+        website: None,
+    };
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("meta_upd"), manager.clone(), EVENT_SCHEMA_VERSION).into_val(&env),
+                MetadataUpdatedEvent {
+                    caller: manager.clone(),
+                    metadata: expected.clone(),
+                    version: EVENT_SCHEMA_VERSION,
+                }.data(&env)
+            )
+        ]
+    );
+    assert_eq!(client.metadata(), expected);
+    assert_eq!(client.name(), expected.name);
+    assert_eq!(client.symbol(), expected.symbol);
+    
+    // El admin también puede, y None borra los campos
+    client.set_metadata(
+        &admin,
+        &String::from_str(&env, "Buen Dia Builders"),
+        &String::from_str(&env, "BDB"),
+        &None,
+        &None,
+        &None,
+        &Some(String::from_str(&env, "https://buendiabuilders.com"))
+    );
+    let updated = client.metadata();
+    assert_eq!(updated.symbol, String::from_str(&env, "BDB"));
+    assert_eq!(updated.description, None);
+    assert_eq!(updated.image_uri, None);
+    assert_eq!(updated.website, Some(String::from_str(&env, "https://buendiabuilders.com")));
+}
+
+/// Test: set_metadata valida permisos y largos
+#[test]
+fn test_set_metadata_validations() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let name = String::from_str(&env, "Builder Token");
+    let symbol = String::from_str(&env, "BDB");
+    
+    assert_eq!(client.try_metadata(), Err(Ok(TokenError::NotInitialized)));
+    client.initialize(&admin, &name, &symbol, &7, &false, &None, &false);
+    
+    env.mock_all_auths();
+    
+    // Sin rol MetadataManager
+    assert_eq!(
+        client.try_set_metadata(&stranger, &name, &symbol, &None, &None, &None, &None),
+        Err(Ok(TokenError::Unauthorized))
+    );
+    
+    // Symbol por encima de MAX_SYMBOL_LENGTH
+    let long_symbol = String::from_str(&env, "ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456");
+    assert_eq!(
+        client.try_set_metadata(&admin, &name, &long_symbol, &None, &None, &None, &None),
+        Err(Ok(TokenError::InvalidMetadata))
+    );
+    
+    // Campo opcional vacío (para borrarlo se usa None)
+    assert_eq!(
+        client.try_set_metadata(
+            &admin,
+            &name,
+            &symbol,
+            &None,
+            &None,
+            &Some(String::from_str(&env, "")),
+            &None
+        ),
+        Err(Ok(TokenError::InvalidMetadata))
+    );
+    
+    assert_eq!(client.name(), name);
+}
+
+/// Test: decode reconstruye los eventos tipados desde (topics, data)
+#[test]
+fn test_event_decode_roundtrip() {
+    let env = Env::default();
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    
+    let events = [
+        TokenEvent::Transfer(TransferEvent { from: alice.clone(), to: bob.clone(), amount: 100 }),
+        TokenEvent::TransferMuxed(TransferMuxedEvent {
+            from: alice.clone(),
+            to: bob.clone(),
+            to_muxed_id: 7,
+            amount: 100,
+        }),
+        TokenEvent::Burn(BurnEvent { from: alice.clone(), amount: 5 }),
+        TokenEvent::BurnFrom(BurnFromEvent {
+            from: alice.clone(),
+            spender: bob.clone(),
+            amount: 5,
+            version: EVENT_SCHEMA_VERSION,
+        }),
+        TokenEvent::Clawback(ClawbackEvent { from: alice.clone(), amount: 5 }),
+        TokenEvent::ClawbackCaller(ClawbackCallerEvent {
+            caller: bob.clone(),
+            from: alice.clone(),
+            amount: 5,
+            version: EVENT_SCHEMA_VERSION,
+        }),
+        TokenEvent::Approve(ApproveEvent {
+            from: alice.clone(),
+            spender: bob.clone(),
+            amount: 50,
+            expiration_ledger: 1000,
+        }),
+        TokenEvent::SetAuthorized(SetAuthorizedEvent { id: bob.clone(), authorize: false }),
+        TokenEvent::RoleGranted(RoleGrantedEvent {
+            role: Role::Oracle,
+            account: bob.clone(),
+            caller: alice.clone(),
+            version: EVENT_SCHEMA_VERSION,
+        }),
+        TokenEvent::Paused(PausedEvent { caller: alice.clone(), version: EVENT_SCHEMA_VERSION }),
+        TokenEvent::Unpaused(UnpausedEvent { caller: alice.clone(), version: EVENT_SCHEMA_VERSION }),
+        TokenEvent::QuotaRemoved(QuotaRemovedEvent { minter: bob.clone(), version: EVENT_SCHEMA_VERSION }),
+        TokenEvent::Migrated(MigratedEvent {
+            from_version: 1,
+            to_version: 2,
+            version: EVENT_SCHEMA_VERSION,
+        }),
+    ];
+    
+    for event in events {
+        let (topics, data) = event.to_raw(&env);
+        assert_eq!(TokenEvent::decode(&env, &topics, &data), Ok(event));
+    }
+    
+    // Los eventos SEP-41 no llevan nada después de sus topics estándar
+    let transfer = TokenEvent::Transfer(TransferEvent { from: alice.clone(), to: bob.clone(), amount: 100 });
+    let topics: Vec<Val> = (symbol_short!("transfer"), alice.clone(), bob.clone()).into_val(&env);
+    assert_eq!(transfer.to_raw(&env).0, topics);
+    let burn = TokenEvent::Burn(BurnEvent { from: alice.clone(), amount: 5 });
+    let topics: Vec<Val> = (symbol_short!("burn"), alice.clone()).into_val(&env);
+    assert_eq!(burn.to_raw(&env).0, topics);
+    let clawback = TokenEvent::Clawback(ClawbackEvent { from: alice.clone(), amount: 5 });
+    let topics: Vec<Val> = (symbol_short!("clawback"), alice.clone()).into_val(&env);
+    assert_eq!(clawback.to_raw(&env).0, topics);
+    
+    // Los de BDB terminan con la versión del esquema
+    let paused = TokenEvent::Paused(PausedEvent { caller: alice.clone(), version: EVENT_SCHEMA_VERSION });
+    let topics: Vec<Val> = (symbol_short!("paused"), alice.clone(), EVENT_SCHEMA_VERSION).into_val(&env);
+    assert_eq!(paused.to_raw(&env).0, topics);
+}
+
+/// Test: los eventos emitidos por el contrato se decodifican, y decode
+/// rechaza eventos sin versión o de otra versión, desconocidos o con
+/// otra data
+#[test]
+fn test_event_decode_emitted() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    client.set_minter_quota(&admin, &admin, &1000, &100);
+    client.mint(&admin, &alice, &400);
+    
+    // Lo publicado coincide con to_raw() de los eventos tipados
+    let expected = [
+        TokenEvent::Mint(MintEvent { to: alice.clone(), amount: 400 }),
+        TokenEvent::MintQuota(MintQuotaEvent {
+            minter: admin.clone(),
+            to: alice.clone(),
+            amount: 400,
+            remaining: 600,
+            version: EVENT_SCHEMA_VERSION,
+        }),
+    ];
+    let mut raw = Vec::new(&env);
+    for event in expected.iter() {
+        let (topics, data) = event.to_raw(&env);
+        assert_eq!(TokenEvent::decode(&env, &topics, &data).as_ref(), Ok(event));
+        raw.push_back((contract_id.clone(), topics, data));
+    }
+    assert_eq!(env.events().all(), raw);
+    
+    // Los eventos SEP-41 no llevan versión
+    let versioned: Vec<Val> = (symbol_short!("mint"), alice.clone(), EVENT_SCHEMA_VERSION).into_val(&env);
+    assert_eq!(
+        TokenEvent::decode(&env, &versioned, &400i128.into_val(&env)),
+        Err(DecodeError::Malformed)
+    );
+    
+    // Evento de BDB sin versión o de otra versión
+    let quota_rm = QuotaRemovedEvent { minter: admin.clone(), version: EVENT_SCHEMA_VERSION }.data(&env);
+    let unversioned: Vec<Val> = (symbol_short!("quota_rm"), admin.clone()).into_val(&env);
+    assert_eq!(
+        TokenEvent::decode(&env, &unversioned, &quota_rm),
+        Err(DecodeError::Malformed)
+    );
+    let next: Vec<Val> = (symbol_short!("quota_rm"), admin.clone(), EVENT_SCHEMA_VERSION + 1).into_val(&env);
+    assert_eq!(
+        TokenEvent::decode(&env, &next, &quota_rm),
+        Err(DecodeError::UnsupportedVersion(EVENT_SCHEMA_VERSION + 1))
+    );
+    
+    let unknown: Vec<Val> = (symbol_short!("swap"),).into_val(&env);
+    assert_eq!(
+        TokenEvent::decode(&env, &unknown, &().into_val(&env)),
+        Err(DecodeError::UnknownEvent)
+    );
+    
+    // Data que no es el map del evento
+    let malformed: Vec<Val> = (symbol_short!("quota_set"), admin.clone(), EVENT_SCHEMA_VERSION).into_val(&env);
+    assert_eq!(
+        TokenEvent::decode(&env, &malformed, &1000i128.into_val(&env)),
+        Err(DecodeError::Malformed)
+    );
+}
+
+/// Test: la versión publicada en contractmeta es EVENT_SCHEMA_VERSION
+///
+/// contractmeta! solo acepta literales, así que se compara la entrada
+/// que genera (XDR de ScMetaEntry::ScMetaV0) con la constante
+#[test]
+fn test_event_schema_contractmeta() {
+    extern crate std;
+    
+    let xdr_string = |bytes: &[u8]| {
+        let mut out = std::vec::Vec::new();
+        out.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+        out.extend_from_slice(bytes);
+        out.resize(out.len().next_multiple_of(4), 0);
+        out
+    };
+    
+    let mut expected = std::vec![0u8; 4];
+    expected.extend(xdr_string(b"event_schema"));
+    expected.extend(xdr_string(std::format!("{}", EVENT_SCHEMA_VERSION).as_bytes()));
+    
+    assert_eq!(&crate::__CONTRACT_KEY_6576656e745f736368656d61[..], &expected[..]);
+}
+
+/// Test: el registro de holders sigue los balances en 0 / distinto de 0
+/// 
+/// Las posiciones no cambian al salir un holder, así que las páginas
+/// ya leídas siguen valiendo
+#[test]
+fn test_holder_registry() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    assert_eq!(client.holder_count(), 0);
+    
+    client.mint(&admin, &alice, &1000);
+    client.mint(&admin, &bob, &500);
+    client.mint(&admin, &alice, &1000);
+    assert_eq!(client.holder_count(), 2);
+    
+    // bob vacía su cuenta: sale del registro y charlie entra al final
+    client.transfer(&bob, &charlie, &500);
+    assert_eq!(client.holder_count(), 2);
+    assert_eq!(
+        client.holders(&0, &10),
+        HolderPage { holders: vec![&env, alice.clone(), charlie.clone()], next: None }
+    );
+    
+    // Se mantiene la limpieza de balances en 0
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().persistent().has(&DataKey::Balance(bob.clone())));
+        assert!(!env.storage().persistent().has(&DataKey::HolderSlot(bob.clone())));
+    });
+    
+    // Página por página: el slot vacío de bob se saltea
+    assert_eq!(
+        client.holders(&0, &1),
+        HolderPage { holders: vec![&env, alice.clone()], next: Some(1) }
+    );
+    assert_eq!(
+        client.holders(&1, &1),
+        HolderPage { holders: vec![&env, charlie.clone()], next: None }
+    );
+    
+    // alice sale y bob vuelve (en un slot nuevo)
+    client.burn(&alice, &2000);
+    client.transfer(&charlie, &bob, &100);
+    assert_eq!(client.holder_count(), 2);
+    assert_eq!(
+        client.holders(&0, &10),
+        HolderPage { holders: vec![&env, charlie.clone(), bob.clone()], next: None }
+    );
+    
+    assert_eq!(client.try_holders(&0, &0), Err(Ok(TokenError::InvalidPageLimit)));
+    assert_eq!(client.try_holders(&0, &101), Err(Ok(TokenError::InvalidPageLimit)));
+}
+
+/// Test: una página lee como máximo MAX_HOLDERS_PAGE slots
+/// 
+/// Con más slots vacíos seguidos que eso, la página viene vacía pero
+/// `next` sigue hasta los holders que quedan
+#[test]
+fn test_holder_pages_skip_freed_slots() {
+    let env = Env::default();
+    let contract_id = env.register(TokenBDB, ());
+    let client = TokenBDBClient::new(&env, &contract_id);
+    
+    let admin = Address::generate(&env);
+    let alice = Address::generate(&env);
+    
+    client.initialize(
+        &admin,
+        &String::from_str(&env, "Token"),
+        &String::from_str(&env, "TOK"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+    
+    env.mock_all_auths();
+    
+    // MAX_HOLDERS_PAGE + 10 cuentas entran y salen; después entra alice
+    for _ in 0..holders::MAX_HOLDERS_PAGE + 10 {
+        let holder = Address::generate(&env);
+        client.mint(&admin, &holder, &1);
+        client.burn(&holder, &1);
+    }
+    client.mint(&admin, &alice, &1);
+    assert_eq!(client.holder_count(), 1);
+    
+    let first = client.holders(&0, &10);
+    assert_eq!(
+        first,
+        HolderPage { holders: Vec::new(&env), next: Some(holders::MAX_HOLDERS_PAGE) }
+    );
+    assert_eq!(
+        client.holders(&first.next.unwrap(), &10),
+        HolderPage { holders: vec![&env, alice.clone()], next: None }
+    );
+}
@@ -0,0 +1,222 @@
+// src/test_sep41.rs
+#![cfg(test)]
+
+//! Suite de compatibilidad SEP-41
+//!
+//! Todas las operaciones estándar se ejecutan a través de
+//! `soroban_sdk::token::TokenClient`, igual que lo haría un DEX o un vault
+//! que no conoce TokenBDB. El cliente propio solo se usa para
+//! initialize y mint, que no son parte de la interfaz.
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Events, MuxedAddress as _},
+    token::TokenClient,
+    symbol_short, vec, Address, Env, IntoVal, Map, MuxedAddress, String, Symbol, Val,
+};
+
+/// Despliega e inicializa BDB y devuelve (contract_id, admin)
+fn setup(env: &Env) -> (Address, Address) {
+    let contract_id = env.register(TokenBDB, ());
+    let admin = Address::generate(env);
+
+    TokenBDBClient::new(env, &contract_id).initialize(
+        &admin,
+        &String::from_str(env, "Builder Token"),
+        &String::from_str(env, "BDB"),
+        &7,
+        &false,
+        &None,
+        &false
+    );
+
+    (contract_id, admin)
+}
+
+/// Mintea `amount` a `to` usando la función administrativa
+fn mint(env: &Env, contract_id: &Address, to: &Address, amount: i128) {
+    let client = TokenBDBClient::new(env, contract_id);
+    client.mint(&client.admin(), to, &amount);
+}
+
+/// Los metadatos se leen con el cliente estándar
+#[test]
+fn test_sep41_metadata() {
+    let env = Env::default();
+    let (contract_id, _) = setup(&env);
+    let token = TokenClient::new(&env, &contract_id);
+
+    assert_eq!(token.name(), String::from_str(&env, "Builder Token"));
+    assert_eq!(token.symbol(), String::from_str(&env, "BDB"));
+    assert_eq!(token.decimals(), 7);
+}
+
+/// transfer mueve balances y emite ["transfer", from, to] con data = amount
+#[test]
+fn test_sep41_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (contract_id, _) = setup(&env);
+    let token = TokenClient::new(&env, &contract_id);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint(&env, &contract_id, &alice, 1000);
+
+    token.transfer(&alice, &bob, &250);
+
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("transfer"), alice.clone(), bob.clone()).into_val(&env),
+                250i128.into_val(&env)
+            )
+        ]
+    );
+    assert_eq!(token.balance(&alice), 750);
+    assert_eq!(token.balance(&bob), 250);
+}
+
+/// Transferir a una cuenta muxed acredita a la dirección base
+/// y reporta el id en el evento
+#[test]
+fn test_sep41_transfer_muxed() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (contract_id, _) = setup(&env);
+    let token = TokenClient::new(&env, &contract_id);
+
+    let alice = Address::generate(&env);
+    let bob = MuxedAddress::generate(&env);
+    mint(&env, &contract_id, &alice, 1000);
+
+    token.transfer(&alice, &bob, &100);
+
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("transfer"), alice.clone(), bob.address()).into_val(&env),
+                Map::<Symbol, Val>::from_array(&env, [
+                    (symbol_short!("amount"), 100i128.into_val(&env)),
+                    (Symbol::new(&env, "to_muxed_id"), bob.id().into_val(&env)),
+                ]).into_val(&env)
+            )
+        ]
+    );
+    assert_eq!(token.balance(&bob.address()), 100);
+}
+
+/// approve + transfer_from con el orden de argumentos de SEP-41
+#[test]
+fn test_sep41_approve_and_transfer_from() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (contract_id, _) = setup(&env);
+    let token = TokenClient::new(&env, &contract_id);
+
+    let alice = Address::generate(&env);
+    let vault = Address::generate(&env);
+    let charlie = Address::generate(&env);
+    mint(&env, &contract_id, &alice, 1000);
+
+    token.approve(&alice, &vault, &300, &1000);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("approve"), alice.clone(), vault.clone()).into_val(&env),
+                (300i128, 1000u32).into_val(&env)
+            )
+        ]
+    );
+    assert_eq!(token.allowance(&alice, &vault), 300);
+
+    token.transfer_from(&vault, &alice, &charlie, &200);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("transfer"), alice.clone(), charlie.clone()).into_val(&env),
+                200i128.into_val(&env)
+            )
+        ]
+    );
+
+    assert_eq!(token.balance(&alice), 800);
+    assert_eq!(token.balance(&charlie), 200);
+    assert_eq!(token.allowance(&alice, &vault), 100);
+}
+
+/// burn y burn_from reducen balance y supply
+#[test]
+fn test_sep41_burn_and_burn_from() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (contract_id, _) = setup(&env);
+    let token = TokenClient::new(&env, &contract_id);
+    let admin_client = TokenBDBClient::new(&env, &contract_id);
+
+    let alice = Address::generate(&env);
+    let redeemer = Address::generate(&env);
+    mint(&env, &contract_id, &alice, 1000);
+
+    token.burn(&alice, &100);
+    assert_eq!(
+        env.events().all(),
+        vec![
+            &env,
+            (
+                contract_id.clone(),
+                (symbol_short!("burn"), alice.clone()).into_val(&env),
+                100i128.into_val(&env)
+            )
+        ]
+    );
+
+    token.approve(&alice, &redeemer, &500, &1000);
+    token.burn_from(&redeemer, &alice, &500);
+
+    assert_eq!(token.balance(&alice), 400);
+    assert_eq!(token.allowance(&alice, &redeemer), 0);
+    assert_eq!(admin_client.total_supply(), 400);
+}
+
+/// Los errores se propagan como errores de contrato con el código de TokenError
+#[test]
+fn test_sep41_errors() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (contract_id, _) = setup(&env);
+    let token = TokenClient::new(&env, &contract_id);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    mint(&env, &contract_id, &alice, 100);
+
+    assert_eq!(
+        token.try_transfer(&alice, &bob, &200),
+        Err(Ok(TokenError::InsufficientBalance.into()))
+    );
+    assert_eq!(
+        token.try_transfer_from(&bob, &alice, &bob, &50),
+        Err(Ok(TokenError::InsufficientAllowance.into()))
+    );
+    assert_eq!(
+        token.try_burn_from(&bob, &alice, &50),
+        Err(Ok(TokenError::InsufficientAllowance.into()))
+    );
+    assert_eq!(
+        token.try_transfer(&alice, &bob, &0),
+        Err(Ok(TokenError::InvalidAmount.into()))
+    );
+}
@@ -0,0 +1,95 @@
+// src/upgrade.rs
+use soroban_sdk::Env;
+
+use crate::admin;
+use crate::allowance;
+use crate::checkpoints;
+use crate::errors::TokenError;
+use crate::events::{MigratedEvent, EVENT_SCHEMA_VERSION};
+use crate::roles;
+use crate::storage::DataKey;
+
+/// Versión del layout de storage que espera este build
+///
+/// Al cambiar el formato de algún dato:
+/// 1. Subir esta constante
+/// 2. Registrar la migración N -> N+1 en `run_migration`
+pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+
+/// Lee la versión de storage guardada
+///
+/// Despliegues anteriores al versionado no tienen la key: versión 0
+pub fn read_schema_version(env: &Env) -> u32 {
+    env.storage().instance()
+        .get(&DataKey::SchemaVersion)
+        .unwrap_or(0)
+}
+
+/// Guarda la versión de storage
+pub fn write_schema_version(env: &Env, version: u32) {
+    env.storage().instance().set(&DataKey::SchemaVersion, &version);
+}
+
+/// Ejecuta en orden todas las migraciones pendientes
+///
+/// Cada paso N -> N+1 corre una sola vez: la versión se guarda
+/// después de cada paso, así una segunda llamada no repite nada.
+/// Devuelve la versión final
+pub fn migrate(env: &Env) -> Result<u32, TokenError> {
+    let mut version = read_schema_version(env);
+    if version >= CURRENT_SCHEMA_VERSION {
+        return Err(TokenError::AlreadyMigrated);
+    }
+
+    while version < CURRENT_SCHEMA_VERSION {
+        run_migration(env, version)?;
+        version += 1;
+        write_schema_version(env, version);
+        MigratedEvent {
+            from_version: version - 1,
+            to_version: version,
+            version: EVENT_SCHEMA_VERSION,
+        }.publish(env);
+    }
+
+    Ok(version)
+}
+
+/// Registro de migraciones: `from` es la versión de origen
+fn run_migration(env: &Env, from: u32) -> Result<(), TokenError> {
+    match from {
+        0 => migrate_v0_to_v1(env),
+        1 => migrate_v1_to_v2(env),
+        _ => Ok(()),
+    }
+}
+
+/// v0 -> v1: despliegues anteriores al sistema de roles
+///
+/// El admin original minteaba por ser DataKey::Admin; ahora mint
+/// exige el rol Minter, así que le otorgamos todos los roles.
+/// También fijamos ClawbackEnabled = false explícitamente: esos
+/// tokens se emitieron sin clawback y así deben quedar.
+/// Los allowances v0 no tenían expiración: vencen
+/// LEGACY_ALLOWANCE_LEDGERS después de esta migración
+fn migrate_v0_to_v1(env: &Env) -> Result<(), TokenError> {
+    let admin = admin::read_admin(env)?;
+    for role in roles::ALL_ROLES {
+        roles::grant(env, role, &admin);
+    }
+    env.storage().instance().set(&DataKey::ClawbackEnabled, &false);
+    allowance::write_legacy_expiration(
+        env,
+        env.ledger().sequence() + allowance::LEGACY_ALLOWANCE_LEDGERS
+    );
+    Ok(())
+}
+
+/// v1 -> v2: despliegues anteriores a los checkpoints
+///
+/// No hay historial de antes del upgrade: fijamos el horizonte en el
+/// ledger actual para que balance_at falle con HistoryPruned en vez
+/// de devolver el balance de hoy para ledgers pasados
+fn migrate_v1_to_v2(env: &Env) -> Result<(), TokenError> {
+    checkpoints::write_horizon(env, env.ledger().sequence())
+}
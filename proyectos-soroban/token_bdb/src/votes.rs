@@ -0,0 +1,83 @@
+// src/votes.rs
+use soroban_sdk::{Address, Env};
+
+use crate::balance;
+use crate::checkpoints;
+use crate::errors::TokenError;
+use crate::events::{VotesChangedEvent, EVENT_SCHEMA_VERSION};
+use crate::storage::{self, CheckpointSeries, DataKey};
+
+/// Lee a quién delegó sus votos `account` (None = no delegó)
+pub fn read_delegate(env: &Env, account: &Address) -> Option<Address> {
+    env.storage().persistent().get(&DataKey::Delegate(account.clone()))
+}
+
+/// Votos actuales de `account` (último checkpoint, 0 si no tiene)
+pub fn get_votes(env: &Env, account: &Address) -> i128 {
+    checkpoints::latest(env, &CheckpointSeries::Votes(account.clone())).unwrap_or(0)
+}
+
+/// Votos de `account` al cierre de `ledger`
+///
+/// Usa el mismo historial que balance_at (mismas reglas de
+/// FutureLedger / HistoryPruned)
+pub fn get_past_votes(env: &Env, account: &Address, ledger: u32) -> Result<i128, TokenError> {
+    checkpoints::value_at(
+        env,
+        &CheckpointSeries::Votes(account.clone()),
+        ledger,
+        get_votes(env, account)
+    )
+}
+
+/// Suma `delta` (positivo o negativo) a los votos de `delegatee`
+///
+/// Emite VotesChangedEvent con (previous, new)
+fn adjust_votes(env: &Env, delegatee: &Address, delta: i128) {
+    if delta == 0 {
+        return;
+    }
+    let previous = get_votes(env, delegatee);
+    let new_votes = previous + delta;
+    checkpoints::record(env, &CheckpointSeries::Votes(delegatee.clone()), previous, new_votes);
+    VotesChangedEvent {
+        delegatee: delegatee.clone(),
+        previous,
+        new_votes,
+        version: EVENT_SCHEMA_VERSION,
+    }.publish(env);
+}
+
+/// Mueve los votos cuando cambia el balance de `account`
+///
+/// Se llama desde balance::write_balance, así transfer, mint, burn
+/// (y cualquier otro camino que toque balances) actualizan los votos
+pub fn on_balance_change(env: &Env, account: &Address, delta: i128) {
+    if let Some(delegatee) = read_delegate(env, account) {
+        adjust_votes(env, &delegatee, delta);
+    }
+}
+
+/// Cambia el delegado de `delegator` y mueve su balance actual
+///
+/// Devuelve el delegado anterior
+pub fn delegate(env: &Env, delegator: &Address, delegatee: &Address) -> Option<Address> {
+    let previous = read_delegate(env, delegator);
+    let key = DataKey::Delegate(delegator.clone());
+    env.storage().persistent().set(&key, delegatee);
+    storage::extend_persistent(env, &key);
+
+    if previous.as_ref() != Some(delegatee) {
+        let amount = balance::read_shares(env, delegator);
+        if let Some(previous) = &previous {
+            adjust_votes(env, previous, -amount);
+        }
+        adjust_votes(env, delegatee, amount);
+    }
+    previous
+}
+
+/// Poda el historial de votos de `account` hasta el horizonte
+pub fn prune_votes(env: &Env, account: &Address) -> u32 {
+    checkpoints::prune(env, &CheckpointSeries::Votes(account.clone()))
+}